@@ -3,16 +3,84 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::spanned::Spanned;
 
+use crate::serde_attrs::{Container, Names, RenameRule, Representation};
+
 proc_easy::easy_token!(update);
 proc_easy::easy_token!(replace);
 proc_easy::easy_token!(append);
+proc_easy::easy_token!(append_unique);
+proc_easy::easy_token!(hash);
 proc_easy::easy_token!(union);
+proc_easy::easy_token!(max);
+proc_easy::easy_token!(min);
+proc_easy::easy_token!(sum);
+proc_easy::easy_token!(any);
+proc_easy::easy_token!(all);
+proc_easy::easy_token!(path);
+proc_easy::easy_token!(with);
+proc_easy::easy_token!(locked);
+proc_easy::easy_token!(error);
+proc_easy::easy_token!(ignore);
+proc_easy::easy_token!(max_len);
+proc_easy::easy_token!(overflow);
+proc_easy::easy_token!(keep_first);
+proc_easy::easy_token!(keep_last);
+proc_easy::easy_token!(separator);
+proc_easy::easy_token!(bound);
+proc_easy::easy_token!(flatten);
+proc_easy::easy_token!(skip);
+proc_easy::easy_token!(validate);
+proc_easy::easy_token!(validate_on);
+proc_easy::easy_token!(load);
+proc_easy::easy_token!(range);
+proc_easy::easy_token!(len);
+proc_easy::easy_token!(pattern);
+proc_easy::easy_token!(one_of);
+proc_easy::easy_token!(default);
+
+proc_easy::easy_argument_value! {
+    struct With {
+        with: with,
+        path: syn::LitStr,
+    }
+}
+
+proc_easy::easy_argument_value! {
+    struct CratePath {
+        krate: syn::Token![crate],
+        path: syn::LitStr,
+    }
+}
+
+proc_easy::easy_parse! {
+    /// How `append_unique` finds values already present.
+    /// Comparison with `PartialEq` is used if not specified.
+    #[allow(dead_code)]
+    enum UniqueBy {
+        Hash(hash),
+    }
+}
+
+proc_easy::easy_argument! {
+    struct AppendUniqueArg {
+        append_unique: append_unique,
+        by: proc_easy::EasyMaybe<proc_easy::EasySubArgument<UniqueBy>>,
+    }
+}
 
 proc_easy::easy_argument_group! {
     enum KindArg {
         Update(update),
         Replace(replace),
         Append(append),
+        AppendUnique(AppendUniqueArg),
+        Max(max),
+        Min(min),
+        Sum(sum),
+        Any(any),
+        All(all),
+        Path(path),
+        With(With),
     }
 }
 
@@ -21,15 +89,37 @@ enum Kind {
     Update,
     Replace,
     Append,
+    AppendUnique,
+    AppendUniqueHash,
+    Max,
+    Min,
+    Sum,
+    Any,
+    All,
+    Path,
+    With(syn::Path),
 }
 
-impl From<Option<KindArg>> for Kind {
-    fn from(value: Option<KindArg>) -> Self {
+impl TryFrom<Option<KindArg>> for Kind {
+    type Error = syn::Error;
+
+    fn try_from(value: Option<KindArg>) -> syn::Result<Self> {
         match value {
-            Some(KindArg::Update(_)) => Kind::Update,
-            Some(KindArg::Replace(_)) => Kind::Replace,
-            Some(KindArg::Append(_)) => Kind::Append,
-            None => Kind::Default,
+            Some(KindArg::Update(_)) => Ok(Kind::Update),
+            Some(KindArg::Replace(_)) => Ok(Kind::Replace),
+            Some(KindArg::Append(_)) => Ok(Kind::Append),
+            Some(KindArg::AppendUnique(arg)) => match arg.by {
+                proc_easy::EasyMaybe::Nothing => Ok(Kind::AppendUnique),
+                proc_easy::EasyMaybe::Just(_) => Ok(Kind::AppendUniqueHash),
+            },
+            Some(KindArg::Max(_)) => Ok(Kind::Max),
+            Some(KindArg::Min(_)) => Ok(Kind::Min),
+            Some(KindArg::Sum(_)) => Ok(Kind::Sum),
+            Some(KindArg::Any(_)) => Ok(Kind::Any),
+            Some(KindArg::All(_)) => Ok(Kind::All),
+            Some(KindArg::Path(_)) => Ok(Kind::Path),
+            Some(KindArg::With(with)) => Ok(Kind::With(with.path.parse()?)),
+            None => Ok(Kind::Default),
         }
     }
 }
@@ -44,7 +134,301 @@ impl ToTokens for Kind {
             Kind::Update => quote::quote!(figa::private::Update),
             Kind::Replace => quote::quote!(figa::private::Replace),
             Kind::Append => quote::quote!(figa::private::Append),
+            Kind::AppendUnique => quote::quote!(figa::private::AppendUnique),
+            Kind::AppendUniqueHash => quote::quote!(figa::private::AppendUniqueHash),
+            Kind::Max => quote::quote!(figa::private::Max),
+            Kind::Min => quote::quote!(figa::private::Min),
+            Kind::Sum => quote::quote!(figa::private::Sum),
+            Kind::Any => quote::quote!(figa::private::Any),
+            Kind::All => quote::quote!(figa::private::All),
+            Kind::Path => quote::quote!(figa::private::ResolvePath),
             Kind::Default => quote::quote!(figa::private::Default),
+            Kind::With(path) => path.to_token_stream(),
+        }
+    }
+}
+
+proc_easy::easy_parse! {
+    enum LockPolicy {
+        Error(error),
+        Ignore(ignore),
+    }
+}
+
+proc_easy::easy_argument! {
+    struct Locked {
+        locked: locked,
+        policy: proc_easy::EasyMaybe<proc_easy::EasySubArgument<LockPolicy>>,
+    }
+}
+
+impl ToTokens for LockPolicy {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(self.to_token_stream());
+    }
+
+    fn to_token_stream(&self) -> TokenStream {
+        match self {
+            LockPolicy::Error(error) => {
+                quote::quote_spanned!(error.span => figa::private::LockPolicy::Error)
+            }
+            LockPolicy::Ignore(ignore) => {
+                quote::quote_spanned!(ignore.span => figa::private::LockPolicy::Ignore)
+            }
+        }
+    }
+}
+
+proc_easy::easy_argument_value! {
+    struct MaxLen {
+        max_len: max_len,
+        value: syn::Expr,
+    }
+}
+
+proc_easy::easy_argument_value! {
+    struct Separator {
+        separator: separator,
+        value: syn::LitStr,
+    }
+}
+
+proc_easy::easy_parse! {
+    enum OverflowPolicy {
+        Error(error),
+        KeepFirst(keep_first),
+        KeepLast(keep_last),
+    }
+}
+
+proc_easy::easy_argument_value! {
+    struct Overflow {
+        overflow: overflow,
+        policy: OverflowPolicy,
+    }
+}
+
+impl ToTokens for OverflowPolicy {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(self.to_token_stream());
+    }
+
+    fn to_token_stream(&self) -> TokenStream {
+        match self {
+            OverflowPolicy::Error(error) => {
+                quote::quote_spanned!(error.span => figa::private::Overflow::Error)
+            }
+            OverflowPolicy::KeepFirst(keep_first) => {
+                quote::quote_spanned!(keep_first.span => figa::private::Overflow::KeepFirst)
+            }
+            OverflowPolicy::KeepLast(keep_last) => {
+                quote::quote_spanned!(keep_last.span => figa::private::Overflow::KeepLast)
+            }
+        }
+    }
+}
+
+proc_easy::easy_argument_value! {
+    struct Bound {
+        bound: bound,
+        value: syn::LitStr,
+    }
+}
+
+impl Bound {
+    fn predicates(&self) -> syn::Result<Vec<syn::WherePredicate>> {
+        let predicates = self.value.parse_with(
+            syn::punctuated::Punctuated::<syn::WherePredicate, syn::Token![,]>::parse_terminated,
+        )?;
+        Ok(predicates.into_iter().collect())
+    }
+}
+
+proc_easy::easy_argument! {
+    struct ValidateArg {
+        validate: validate,
+        path: proc_easy::EasyMaybe<proc_easy::EasySubArgument<syn::LitStr>>,
+    }
+}
+
+/// How a field or the value itself is validated.
+enum Validator {
+    /// Field type implements `Validate`.
+    Nested,
+    /// Function that accepts reference to the value.
+    With(syn::Path),
+}
+
+impl TryFrom<ValidateArg> for Validator {
+    type Error = syn::Error;
+
+    fn try_from(value: ValidateArg) -> syn::Result<Self> {
+        match value.path {
+            proc_easy::EasyMaybe::Nothing => Ok(Validator::Nested),
+            proc_easy::EasyMaybe::Just(
+                proc_easy::EasySubArgument::Value(path) | proc_easy::EasySubArgument::Tuple(path),
+            ) => Ok(Validator::With(path.parse()?)),
+        }
+    }
+}
+
+proc_easy::easy_parse! {
+    /// Tokens are kept only to parse the attribute.
+    #[allow(dead_code)]
+    enum ValidationPoint {
+        Update(update),
+        Load(load),
+    }
+}
+
+proc_easy::easy_argument_value! {
+    struct ValidateOn {
+        validate_on: validate_on,
+        point: ValidationPoint,
+    }
+}
+
+proc_easy::easy_argument_value! {
+    struct RangeArg {
+        range: range,
+        value: syn::ExprRange,
+    }
+}
+
+proc_easy::easy_argument_value! {
+    struct LenArg {
+        len: len,
+        value: syn::ExprRange,
+    }
+}
+
+proc_easy::easy_argument_value! {
+    struct PatternArg {
+        pattern: pattern,
+        value: syn::LitStr,
+    }
+}
+
+proc_easy::easy_argument_value! {
+    struct OneOfArg {
+        one_of: one_of,
+        value: syn::ExprArray,
+    }
+}
+
+proc_easy::easy_argument_value! {
+    struct DefaultArg {
+        default: default,
+        value: syn::Expr,
+    }
+}
+
+/// Declarative check of the field value.
+enum Constraint {
+    Range(syn::ExprRange),
+    Len(syn::ExprRange),
+    Pattern(syn::LitStr),
+    OneOf(syn::ExprArray),
+}
+
+impl Constraint {
+    /// Expression that checks the value behind reference `value`.
+    /// Evaluates to `Result<(), String>`.
+    /// Optional value is checked only if it is `Some`.
+    fn check(&self, value: &TokenStream, optional: bool) -> TokenStream {
+        if optional {
+            let check = self.check(&quote::quote!(__value), false);
+            return quote::quote! {
+                match #value {
+                    figa::private::Some(__value) => #check,
+                    figa::private::None => figa::private::Ok(()),
+                }
+            };
+        }
+
+        match self {
+            Constraint::Range(range) => {
+                quote::quote_spanned!(range.span() => figa::private::check_range(#value, #range))
+            }
+            Constraint::Len(range) => {
+                quote::quote_spanned!(range.span() => figa::private::check_len(#value, #range))
+            }
+            Constraint::Pattern(pattern) => quote::quote_spanned! {pattern.span() =>
+                {
+                    static PATTERN: figa::private::Pattern = figa::private::Pattern::new();
+                    figa::private::check_pattern(#value, &PATTERN, #pattern)
+                }
+            },
+            Constraint::OneOf(allowed) => {
+                quote::quote_spanned!(allowed.span() => figa::private::check_one_of(#value, &#allowed))
+            }
+        }
+    }
+
+    /// `figa::Constraint` that describes this constraint of field with value type `ty`.
+    /// Bounds and allowed values are evaluated and converted to `figa::ConstraintValue`.
+    fn describe(&self, ty: &syn::Type) -> TokenStream {
+        let value = |expr: &TokenStream| {
+            quote::quote_spanned! {expr.span() =>
+                {
+                    #[allow(unused_imports)]
+                    use figa::private::{ValueByConversion as _, ValueByDebug as _};
+                    (&figa::private::ValueProbe(#expr)).constraint_value()
+                }
+            }
+        };
+
+        let bounds = |range: &syn::ExprRange, convert: &dyn Fn(&syn::Expr) -> TokenStream| {
+            let start = match &range.start {
+                None => quote::quote!(figa::private::Bound::Unbounded),
+                Some(start) => {
+                    let start = convert(start);
+                    quote::quote!(figa::private::Bound::Included(#start))
+                }
+            };
+            let end = match (&range.end, &range.limits) {
+                (None, _) => quote::quote!(figa::private::Bound::Unbounded),
+                (Some(end), syn::RangeLimits::HalfOpen(_)) => {
+                    let end = convert(end);
+                    quote::quote!(figa::private::Bound::Excluded(#end))
+                }
+                (Some(end), syn::RangeLimits::Closed(_)) => {
+                    let end = convert(end);
+                    quote::quote!(figa::private::Bound::Included(#end))
+                }
+            };
+            quote::quote!(start: #start, end: #end)
+        };
+
+        match self {
+            Constraint::Range(range) => {
+                // Bounds have the type of the field value, as in `check_range`.
+                let bounds = bounds(range, &|bound| {
+                    value(
+                        &quote::quote_spanned!(bound.span() => { let __bound: &#ty = &(#bound); __bound }),
+                    )
+                });
+                quote::quote!(figa::Constraint::Range { #bounds })
+            }
+            Constraint::Len(range) => {
+                let bounds = bounds(
+                    range,
+                    &|bound| quote::quote_spanned!(bound.span() => #bound),
+                );
+                quote::quote!(figa::Constraint::Len { #bounds })
+            }
+            Constraint::Pattern(pattern) => quote::quote!(figa::Constraint::Pattern(#pattern)),
+            Constraint::OneOf(allowed) => {
+                let values = (0..allowed.elems.len())
+                    .map(|idx| value(&quote::quote!(&__allowed[#idx])))
+                    .collect::<Vec<_>>();
+                quote::quote_spanned! {allowed.span() =>
+                    figa::Constraint::OneOf({
+                        let __allowed = figa::private::one_of_values::<#ty, _>(&#allowed);
+                        figa::private::Vec::from([#(#values,)*])
+                    })
+                }
+            }
         }
     }
 }
@@ -53,229 +437,1581 @@ proc_easy::easy_attributes! {
     @(figa)
     struct FigaAttributes {
         kind: Option<KindArg>,
+        locked: Option<Locked>,
+        max_len: Option<MaxLen>,
+        overflow: Option<Overflow>,
+        separator: Option<Separator>,
+        bound: Option<Bound>,
+        flatten: Option<flatten>,
+        skip: Option<skip>,
+        validate: Option<ValidateArg>,
+        range: Option<RangeArg>,
+        len: Option<LenArg>,
+        pattern: Option<PatternArg>,
+        one_of: Option<OneOfArg>,
+        default: Option<DefaultArg>,
     }
 }
 
-pub fn derive(input: syn::DeriveInput) -> Result<TokenStream, syn::Error> {
-    let ident = &input.ident;
-    let ident_lit = syn::LitStr::new(&ident.to_string(), ident.span());
-    match input.data {
-        syn::Data::Enum(_) => Err(syn::Error::new_spanned(
-            input,
-            "Figa can only be derived for structs",
-        )),
-        syn::Data::Union(_) => Err(syn::Error::new_spanned(
-            input,
-            "Figa can only be derived for structs",
-        )),
-        syn::Data::Struct(data) => match data.fields {
-            syn::Fields::Unit => Ok(quote::quote! {
-                impl figa::Figa for #ident {
-                    fn update<'de, D>(&mut self, deserializer: D) -> figa::private::Result<(), D::Error>
-                    where
-                        D: figa::private::Deserializer<'de>,
-                    {
-                        deserializer.deserialize_unit_struct(#ident_lit, figa::private::UnitStructVisitor)
-                    }
+proc_easy::easy_attributes! {
+    @(figa)
+    struct ContainerAttributes {
+        bound: Option<Bound>,
+        krate: Option<CratePath>,
+        validate: Option<ValidateArg>,
+        validate_on: Option<ValidateOn>,
+        default: Option<default>,
+    }
+}
+
+/// How a field is updated from the next layer.
+struct Strategy {
+    kind: Kind,
+    locked: Option<LockPolicy>,
+    max_len: Option<(syn::Expr, OverflowPolicy)>,
+    separator: Option<syn::LitStr>,
+    bound: Option<Vec<syn::WherePredicate>>,
+    flatten: bool,
+    /// Field is never updated from layers.
+    skip: bool,
+    validate: Option<Validator>,
+    constraints: Vec<Constraint>,
+    /// Value of the field in generated `Default` implementation.
+    default: Option<syn::Expr>,
+}
+
+impl Strategy {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let attrs = FigaAttributes::parse(&field.attrs, field.span())?;
+
+        let locked = attrs.locked.map(|locked| match locked.policy {
+            proc_easy::EasyMaybe::Nothing => LockPolicy::Error(error(locked.locked.span)),
+            proc_easy::EasyMaybe::Just(
+                proc_easy::EasySubArgument::Value(policy)
+                | proc_easy::EasySubArgument::Tuple(policy),
+            ) => policy,
+        });
+
+        let max_len = match (attrs.max_len, attrs.overflow) {
+            (None, None) => None,
+            (None, Some(overflow)) => {
+                return Err(syn::Error::new(
+                    overflow.overflow.span,
+                    "`overflow` requires `max_len`",
+                ))
+            }
+            (Some(max_len), None) => Some((
+                max_len.value,
+                OverflowPolicy::Error(error(max_len.max_len.span)),
+            )),
+            (Some(max_len), Some(overflow)) => Some((max_len.value, overflow.policy)),
+        };
+
+        let kind = Kind::try_from(attrs.kind)?;
+
+        let separator = match attrs.separator {
+            None => None,
+            Some(separator) => match kind {
+                Kind::Append => Some(separator.value),
+                _ => {
+                    return Err(syn::Error::new(
+                        separator.separator.span,
+                        "`separator` requires `append`",
+                    ))
                 }
-            }),
-            syn::Fields::Named(fields) => {
-                let field_names = fields
-                    .named
-                    .iter()
-                    .map(|field| field.ident.as_ref().unwrap())
-                    .collect::<Vec<_>>();
+            },
+        };
 
-                let field_name_lits = fields
-                    .named
-                    .iter()
-                    .map(|field| {
-                        syn::LitStr::new(&field.ident.as_ref().unwrap().to_string(), field.span())
-                    })
-                    .collect::<Vec<_>>();
+        let bound = attrs.bound.map(|bound| bound.predicates()).transpose()?;
 
-                let field_kinds = fields
-                    .named
-                    .iter()
-                    .map(|field| -> syn::Result<_> {
-                        let attrs = FigaAttributes::parse(&field.attrs, field.span())?;
-                        Ok(Kind::from(attrs.kind))
-                    })
-                    .collect::<Result<Vec<_>, _>>()?;
+        let flatten = match attrs.flatten {
+            None => false,
+            Some(flatten) if field.ident.is_none() => {
+                return Err(syn::Error::new(
+                    flatten.span,
+                    "`flatten` requires named field",
+                ))
+            }
+            Some(flatten) if locked.is_some() => {
+                return Err(syn::Error::new(
+                    flatten.span,
+                    "`flatten` cannot be combined with `locked`",
+                ))
+            }
+            Some(_) => true,
+        };
 
-                let field_next_seq_element = fields
-                    .named
-                    .iter()
-                    .zip(&field_kinds)
-                    .map(|(field, kind)|{
-                        let ident = field.ident.as_ref().unwrap();
-                        quote::quote_spanned! {field.span() => seq.next_element_seed(#kind(&mut me.#ident))}
-                    })
-                    .collect::<Vec<_>>();
+        let skip = match attrs.skip {
+            None => false,
+            Some(skip)
+                if !matches!(kind, Kind::Default)
+                    || locked.is_some()
+                    || max_len.is_some()
+                    || flatten =>
+            {
+                return Err(syn::Error::new(
+                    skip.span,
+                    "`skip` cannot be combined with other update attributes",
+                ))
+            }
+            Some(_) => true,
+        };
 
-                let field_next_map_value = fields
-                    .named
-                    .iter()
-                    .zip(&field_kinds)
-                    .map(|(field, kind)| {
-                        let ident = field.ident.as_ref().unwrap();
-                        quote::quote_spanned! {field.span() => map.next_value_seed(#kind(&mut me.#ident))}
-                    })
-                    .collect::<Vec<_>>();
+        // Invalid regular expression is rejected here instead of failing every layer.
+        if let Some(pattern) = &attrs.pattern {
+            if let Err(err) = regex_syntax::Parser::new().parse(&pattern.value.value()) {
+                return Err(syn::Error::new(
+                    pattern.value.span(),
+                    format_args!("invalid `pattern`: {}", err),
+                ));
+            }
+        }
 
-                Ok(quote::quote! {
-                    impl figa::Figa for #ident {
-                        fn update<'de, D>(&mut self, deserializer: D) -> figa::private::Result<(), D::Error>
-                        where
-                            D: figa::private::Deserializer<'de>,
-                        {
-                            #[allow(non_camel_case_types)]
-                            enum FieldIdent {
-                                #(
-                                    #field_names,
-                                )*
-                            }
+        let constraints = attrs
+            .range
+            .map(|range| Constraint::Range(range.value))
+            .into_iter()
+            .chain(attrs.len.map(|len| Constraint::Len(len.value)))
+            .chain(
+                attrs
+                    .pattern
+                    .map(|pattern| Constraint::Pattern(pattern.value)),
+            )
+            .chain(attrs.one_of.map(|one_of| Constraint::OneOf(one_of.value)))
+            .collect();
 
-                            struct FieldIdentVisitor;
+        Ok(Strategy {
+            kind,
+            locked,
+            max_len,
+            separator,
+            bound,
+            flatten,
+            skip,
+            validate: attrs.validate.map(Validator::try_from).transpose()?,
+            constraints,
+            default: attrs.default.map(|default| default.value),
+        })
+    }
 
-                            impl<'de> figa::private::Visitor<'de> for FieldIdentVisitor {
-                                type Value = FieldIdent;
+    /// Bounds required to update the field with this strategy.
+    /// Inferred only for fields which type mentions type parameters,
+    /// unless overridden with `#[figa(bound = "...")]`.
+    fn bounds(&self, field: &syn::Field, type_params: &[&syn::Ident]) -> Vec<syn::WherePredicate> {
+        if let Some(bound) = &self.bound {
+            return bound.clone();
+        }
 
-                                fn expecting(&self, formatter: &mut figa::private::Formatter) -> figa::private::FmtResult {
-                                    formatter.write_str("field identifier")
-                                }
+        let ty = &field.ty;
+        if self.skip || !mentions(ty.to_token_stream(), type_params) {
+            return Vec::new();
+        }
 
-                                fn visit_str<E>(self, value: &figa::private::str) -> figa::private::Result<FieldIdent, E>
-                                where
-                                    E: figa::private::DeError,
-                                {
-                                    match value {
-                                        #(
-                                            #field_name_lits => figa::private::Ok(FieldIdent::#field_names),
-                                        )*
-                                        _ => figa::private::Err(figa::private::DeError::unknown_field(value, &[#(#field_name_lits,)*])),
-                                    }
-                                }
-                            }
+        let mut bounds = Vec::new();
+        match (&self.kind, &self.separator) {
+            (_, Some(_)) | (Kind::With(_), _) => {}
+            (Kind::Default, _) => bounds.push(syn::parse_quote!(#ty: figa::Figa)),
+            (kind, None) => bounds.push(syn::parse_quote! {
+                for<'__de, '__b> #kind<'__b, #ty>: figa::private::DeserializeSeed<'__de, Value = ()>
+            }),
+        }
 
-                            impl<'de> figa::private::Deserialize<'de> for FieldIdent {
-                                fn deserialize<D>(deserializer: D) -> figa::private::Result<Self, D::Error>
-                                where
-                                    D: figa::private::Deserializer<'de>,
-                                {
-                                    deserializer.deserialize_identifier(FieldIdentVisitor)
-                                }
-                            }
+        if self.max_len.is_some() {
+            bounds.push(syn::parse_quote!(#ty: figa::private::Bounded));
+        }
 
-                            struct Visitor<'a>(&'a mut #ident);
+        if !self.constraints.is_empty() {
+            bounds.push(syn::parse_quote!(#ty: figa::private::Clone));
+        }
 
-                            impl<'de> figa::private::Visitor<'de> for Visitor<'_> {
-                                type Value = ();
+        bounds
+    }
 
-                                fn expecting(&self, formatter: &mut figa::private::Formatter) -> figa::private::FmtResult {
-                                    formatter.write_str("struct")
-                                }
+    /// Generates statements that call `next` with seed to update the field at `place`.
+    /// `next` is `map.next_value_seed`, `seq.next_element_seed`, `access.newtype_variant_seed`
+    /// or `unknown.select(..).deserialize_seed` for flattened fields.
+    fn update(
+        &self,
+        field: &syn::Field,
+        place: &TokenStream,
+        name: &str,
+        next: TokenStream,
+    ) -> TokenStream {
+        if self.constraints.is_empty() {
+            return self.update_in_place(field, place, name, next);
+        }
 
-                                fn visit_unit<E>(self) -> figa::private::Result<(), E> {
-                                    figa::private::Ok(())
-                                }
+        // Constrained field is updated on a copy that is assigned only if constraints hold,
+        // so the layer with invalid value leaves the field unchanged.
+        let update = self.update_in_place(field, &quote::quote!(__value), name, next);
+        quote::quote_spanned! {field.span() =>
+            {
+                let mut __value = figa::private::Clone::clone(&#place);
+                #update
+                #place = __value;
+            }
+        }
+    }
 
-                                fn visit_seq<A>(self, mut seq: A) -> figa::private::Result<(), A::Error>
-                                where
-                                    A: figa::private::SeqAccess<'de>,
-                                {
-                                    let me = self.0;
-                                    #(
-                                        #field_next_seq_element?;
-                                    )*
-                                    figa::private::Ok(())
-                                }
+    /// Generates statements that update the field at `place` and check its constraints.
+    fn update_in_place(
+        &self,
+        field: &syn::Field,
+        place: &TokenStream,
+        name: &str,
+        next: TokenStream,
+    ) -> TokenStream {
+        let seed = match &self.separator {
+            // Types with `UpdateElements` implementation are updated element-wise,
+            // others with their `Figa` implementation.
+            None if matches!(self.kind, Kind::Update) => {
+                let ty = &field.ty;
+                quote::quote_spanned! {field.span() =>
+                    {
+                        #[allow(unused_imports)]
+                        use figa::private::{UpdateByElements as _, UpdateByFiga as _};
+                        (&figa::private::UpdateProbe::<#ty>(figa::private::PhantomData)).update_seed(&mut #place)
+                    }
+                }
+            }
+            None => {
+                let kind = &self.kind;
+                quote::quote_spanned! {field.span() => #kind(&mut #place)}
+            }
+            Some(separator) => {
+                quote::quote_spanned! {field.span() => figa::private::AppendSeparated(&mut #place, #separator)}
+            }
+        };
 
-                                fn visit_map<A>(self, mut map: A) -> figa::private::Result<(), A::Error>
-                                where
-                                    A: figa::private::MapAccess<'de>,
-                                {
-                                    let me = self.0;
-                                    while let Some(key) = map.next_key::<FieldIdent>()? {
-                                        match key {
-                                            #(
-                                                FieldIdent::#field_names => {
-                                                    #field_next_map_value?;
-                                                }
-                                            )*
-                                        }
-                                    }
-                                    figa::private::Ok(())
-                                }
-                            }
+        let name_lit = syn::LitStr::new(name, field.span());
 
-                            deserializer.deserialize_struct(#ident_lit, &[#(#field_name_lits,)*], Visitor(self))?;
+        let mut update = match &self.locked {
+            None => quote::quote_spanned! {field.span() => #next(#seed)?;},
+            Some(policy) => {
+                let locked = quote::quote_spanned! {field.span() =>
+                    #next(figa::private::Locked { field: #name_lit, policy: #policy })?;
+                };
 
-                            figa::private::Ok(())
+                if is_option(&field.ty) {
+                    quote::quote_spanned! {field.span() =>
+                        if #place.is_none() {
+                            #next(#seed)?;
+                        } else {
+                            #locked
                         }
                     }
-                })
+                } else {
+                    locked
+                }
             }
-            syn::Fields::Unnamed(fields) => {
-                let fields_count = fields.unnamed.len();
-                let field_kinds = fields
-                    .unnamed
-                    .iter()
-                    .map(|field| -> syn::Result<_> {
-                        let attrs = FigaAttributes::parse(&field.attrs, field.span())?;
-                        Ok(Kind::from(attrs.kind))
-                    })
-                    .collect::<Result<Vec<_>, _>>()?;
+        };
 
-                let field_next_seq_element = fields
-                    .unnamed
-                    .iter()
-                    .zip(&field_kinds)
-                    .enumerate()
-                    .map(|(idx, (field, kind))|{
-                        let index = syn::Index::from(idx);
-                        quote::quote_spanned! {field.span() => seq.next_element_seed(#kind(&mut me.#index))}
-                    })
-                    .collect::<Vec<_>>();
+        if let Some((max_len, overflow)) = &self.max_len {
+            update.extend(quote::quote_spanned! {field.span() =>
+                figa::private::bound::<_, __A::Error>(&mut #place, #max_len, #overflow, #name_lit)?;
+            });
+        }
+
+        // Constraints are checked as soon as the field is updated,
+        // so the layer that introduced invalid value fails to load.
+        let optional = is_option(&field.ty);
+        for constraint in &self.constraints {
+            let check = constraint.check(&quote::quote!(&#place), optional);
+            update.extend(quote::quote_spanned! {field.span() =>
+                figa::private::constrained::<__A::Error>(#check, #name_lit)?;
+            });
+        }
+
+        update
+    }
+}
 
-                Ok(quote::quote! {
-                    impl figa::Figa for #ident {
-                        fn update<'de, D>(&mut self, deserializer: D) -> figa::private::Result<(), D::Error>
-                        where
-                            D: figa::private::Deserializer<'de>,
-                        {
-                            struct Visitor<'a>(&'a mut #ident);
+/// Code that updates fields of a struct or enum variant.
+struct FieldUpdates {
+    /// Identifiers of named fields.
+    idents: Vec<syn::Ident>,
+    /// Names of named fields as they appear in the input.
+    names: Vec<syn::LitStr>,
+    /// Names and aliases of named fields.
+    aliases: Vec<Vec<syn::LitStr>>,
+    next_seq_element: Vec<TokenStream>,
+    next_map_value: Vec<TokenStream>,
+    /// Updates of flattened fields from unknown entries of the map.
+    flatten: Vec<TokenStream>,
+}
 
-                            impl<'de> figa::private::Visitor<'de> for Visitor<'_> {
-                                type Value = ();
+impl FieldUpdates {
+    /// `places` are expressions that refer to the fields being updated.
+    /// `rule` renames fields as `#[serde(rename_all)]` does.
+    fn new(fields: &syn::Fields, places: &[TokenStream], rule: RenameRule) -> syn::Result<Self> {
+        let mut updates = FieldUpdates {
+            idents: Vec::new(),
+            names: Vec::new(),
+            aliases: Vec::new(),
+            next_seq_element: Vec::new(),
+            next_map_value: Vec::new(),
+            flatten: Vec::new(),
+        };
 
-                                fn expecting(&self, formatter: &mut figa::private::Formatter) -> figa::private::FmtResult {
-                                    formatter.write_str("struct")
-                                }
+        for (idx, (field, place)) in fields.iter().zip(places).enumerate() {
+            let strategy = Strategy::parse(field)?;
+            let names = Names::field(field, idx, rule)?;
+            let name = names.name.value();
 
-                                fn visit_unit<E>(self) -> figa::private::Result<(), E> {
-                                    figa::private::Ok(())
-                                }
+            if strategy.skip {
+                continue;
+            }
 
-                                fn visit_seq<A>(self, mut seq: A) -> figa::private::Result<(), A::Error>
-                                where
-                                    A: figa::private::SeqAccess<'de>,
-                                {
-                                    let me = self.0;
-                                    #(
-                                        #field_next_seq_element?;
-                                    )*
-                                    figa::private::Ok(())
-                                }
-                            }
+            if strategy.flatten {
+                let filter = flat_filter(&field.ty);
+                updates.flatten.push(strategy.update(
+                    field,
+                    place,
+                    &name,
+                    quote::quote!(unknown.select(#filter).deserialize_seed::<_, __A::Error>),
+                ));
+                continue;
+            }
+
+            updates.next_seq_element.push(strategy.update(
+                field,
+                place,
+                &name,
+                quote::quote!(seq.next_element_seed),
+            ));
+            updates.next_map_value.push(strategy.update(
+                field,
+                place,
+                &name,
+                quote::quote!(map.next_value_seed),
+            ));
+
+            if let Some(ident) = &field.ident {
+                updates.idents.push(ident.clone());
+                updates.names.push(names.name);
+                updates.aliases.push(names.all);
+            }
+        }
+
+        Ok(updates)
+    }
+
+    /// Names and aliases of the fields, as passed to `Deserializer::deserialize_struct`.
+    fn fields(&self) -> TokenStream {
+        let aliases = &self.aliases;
+        quote::quote!(&[#(#(#aliases,)*)*])
+    }
+
+    /// Number of fields in sequence form.
+    fn seq_len(&self) -> usize {
+        self.next_seq_element.len()
+    }
+
+    fn has_flatten(&self) -> bool {
+        !self.flatten.is_empty()
+    }
+
+    /// Field identifier enum with `Deserialize` implementation.
+    /// With flattened fields unknown identifiers are kept for them.
+    fn field_ident(&self) -> TokenStream {
+        let FieldUpdates {
+            idents,
+            names,
+            aliases,
+            ..
+        } = self;
+
+        if self.has_flatten() {
+            return quote::quote! {
+                #[allow(non_camel_case_types)]
+                enum FieldIdent<'de> {
+                    #(
+                        #idents,
+                    )*
+                    __other(figa::private::Content<'de>),
+                }
 
-                            deserializer.deserialize_tuple_struct(#ident_lit, #fields_count, Visitor(self))?;
+                struct FieldIdentVisitor;
 
-                            figa::private::Ok(())
+                impl<'de> figa::private::Visitor<'de> for FieldIdentVisitor {
+                    type Value = FieldIdent<'de>;
+
+                    fn expecting(&self, formatter: &mut figa::private::Formatter) -> figa::private::FmtResult {
+                        formatter.write_str("field identifier")
+                    }
+
+                    fn visit_str<E>(self, value: &figa::private::str) -> figa::private::Result<FieldIdent<'de>, E>
+                    where
+                        E: figa::private::DeError,
+                    {
+                        match value {
+                            #(
+                                #(#aliases)|* => figa::private::Ok(FieldIdent::#idents),
+                            )*
+                            _ => figa::private::Ok(FieldIdent::__other(figa::private::Content::String(value.into()))),
+                        }
+                    }
+
+                    fn visit_borrowed_str<E>(self, value: &'de figa::private::str) -> figa::private::Result<FieldIdent<'de>, E>
+                    where
+                        E: figa::private::DeError,
+                    {
+                        match value {
+                            #(
+                                #(#aliases)|* => figa::private::Ok(FieldIdent::#idents),
+                            )*
+                            _ => figa::private::Ok(FieldIdent::__other(figa::private::Content::Str(value))),
                         }
                     }
-                })
+                }
+
+                impl<'de> figa::private::Deserialize<'de> for FieldIdent<'de> {
+                    fn deserialize<__D>(deserializer: __D) -> figa::private::Result<Self, __D::Error>
+                    where
+                        __D: figa::private::Deserializer<'de>,
+                    {
+                        deserializer.deserialize_identifier(FieldIdentVisitor)
+                    }
+                }
+            };
+        }
+
+        quote::quote! {
+            #[allow(non_camel_case_types)]
+            enum FieldIdent {
+                #(
+                    #idents,
+                )*
+            }
+
+            struct FieldIdentVisitor;
+
+            impl<'de> figa::private::Visitor<'de> for FieldIdentVisitor {
+                type Value = FieldIdent;
+
+                fn expecting(&self, formatter: &mut figa::private::Formatter) -> figa::private::FmtResult {
+                    formatter.write_str("field identifier")
+                }
+
+                fn visit_str<E>(self, value: &figa::private::str) -> figa::private::Result<FieldIdent, E>
+                where
+                    E: figa::private::DeError,
+                {
+                    match value {
+                        #(
+                            #(#aliases)|* => figa::private::Ok(FieldIdent::#idents),
+                        )*
+                        _ => figa::private::Err(figa::private::DeError::unknown_field(value, &[#(#names,)*])),
+                    }
+                }
             }
-        },
+
+            impl<'de> figa::private::Deserialize<'de> for FieldIdent {
+                fn deserialize<__D>(deserializer: __D) -> figa::private::Result<Self, __D::Error>
+                where
+                    __D: figa::private::Deserializer<'de>,
+                {
+                    deserializer.deserialize_identifier(FieldIdentVisitor)
+                }
+            }
+        }
     }
+
+    /// `visit_seq` method of the visitor.
+    /// `bind` brings field places into scope.
+    fn visit_seq(&self, bind: &TokenStream) -> TokenStream {
+        let next_seq_element = &self.next_seq_element;
+
+        quote::quote! {
+            fn visit_seq<__A>(self, mut seq: __A) -> figa::private::Result<(), __A::Error>
+            where
+                __A: figa::private::SeqAccess<'de>,
+            {
+                #bind
+                #(
+                    #next_seq_element
+                )*
+                figa::private::Ok(())
+            }
+        }
+    }
+
+    /// `visit_map` method of the visitor, requires field identifier enum.
+    /// `bind` brings field places into scope.
+    fn visit_map(&self, bind: &TokenStream) -> TokenStream {
+        let FieldUpdates {
+            idents,
+            names,
+            next_map_value,
+            flatten,
+            ..
+        } = self;
+
+        if self.has_flatten() {
+            return quote::quote! {
+                fn visit_map<__A>(self, mut map: __A) -> figa::private::Result<(), __A::Error>
+                where
+                    __A: figa::private::MapAccess<'de>,
+                {
+                    #bind
+                    let mut unknown = figa::private::FlatEntries::new();
+                    while let Some(key) = map.next_key::<FieldIdent<'de>>()? {
+                        match key {
+                            #(
+                                FieldIdent::#idents => {
+                                    #next_map_value
+                                }
+                            )*
+                            FieldIdent::__other(key) => {
+                                unknown.push(key, map.next_value()?);
+                            }
+                        }
+                    }
+                    #(
+                        #flatten
+                    )*
+                    unknown.end(&[#(#names,)*])
+                }
+            };
+        }
+
+        quote::quote! {
+            fn visit_map<__A>(self, mut map: __A) -> figa::private::Result<(), __A::Error>
+            where
+                __A: figa::private::MapAccess<'de>,
+            {
+                #bind
+                while let Some(key) = map.next_key::<FieldIdent>()? {
+                    match key {
+                        #(
+                            FieldIdent::#idents => {
+                                #next_map_value
+                            }
+                        )*
+                    }
+                }
+                figa::private::Ok(())
+            }
+        }
+    }
+}
+
+/// Expression with keys filter for flattened field of type `ty`.
+/// Types with `FlatFields` implementation take only their keys, others take all remaining entries.
+fn flat_filter(ty: &syn::Type) -> TokenStream {
+    quote::quote_spanned! {ty.span() =>
+        {
+            #[allow(unused_imports)]
+            use figa::private::{FlatByAny as _, FlatByFields as _};
+            (&figa::private::FlatProbe::<#ty>(figa::private::PhantomData)).flat_fields()
+        }
+    }
+}
+
+/// Checks if tokens mention any of the type parameters.
+fn mentions(tokens: TokenStream, type_params: &[&syn::Ident]) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        proc_macro2::TokenTree::Ident(ident) => type_params.contains(&&ident),
+        proc_macro2::TokenTree::Group(group) => mentions(group.stream(), type_params),
+        _ => false,
+    })
+}
+
+/// Returns `T` if type is syntactically `Option<T>`.
+fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    if !is_option(ty) {
+        return None;
+    }
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    match &path.path.segments.last()?.arguments {
+        syn::PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+/// Checks if type is syntactically `Option<T>`.
+fn is_option(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+/// Type `Figa` is derived for.
+struct Target<'a> {
+    ident: &'a syn::Ident,
+    serde: Container,
+    generics: &'a syn::Generics,
+    /// Generics with bounds required by the fields.
+    bounded: syn::Generics,
+}
+
+impl Target<'_> {
+    fn ty(&self) -> TokenStream {
+        let ident = self.ident;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        quote::quote!(#ident #ty_generics)
+    }
+
+    /// Declares `Visitor` struct with `fields` and implements `Visitor` trait for it with `methods`.
+    /// Visitor has `'__a` lifetime and the generics of the target.
+    /// Inferred bounds are placed on the impl only, so that constructing the visitor doesn't require them.
+    fn visitor(&self, fields: TokenStream, tuple: bool, methods: TokenStream) -> TokenStream {
+        let mut generics = self.generics.clone();
+        generics.params.insert(0, syn::parse_quote!('__a));
+        let (decl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let decl = if tuple {
+            quote::quote!(struct Visitor #decl_generics #fields #where_clause;)
+        } else {
+            quote::quote!(struct Visitor #decl_generics #where_clause #fields)
+        };
+
+        let mut bounded = self.bounded.clone();
+        bounded.params.insert(0, syn::parse_quote!('__a));
+        bounded.params.insert(0, syn::parse_quote!('de));
+        let (impl_generics, _, where_clause) = bounded.split_for_impl();
+
+        quote::quote! {
+            #decl
+
+            impl #impl_generics figa::private::Visitor<'de> for Visitor #ty_generics #where_clause {
+                type Value = ();
+
+                #methods
+            }
+        }
+    }
+}
+
+pub fn derive(input: syn::DeriveInput) -> Result<TokenStream, syn::Error> {
+    let attrs = ContainerAttributes::parse(&input.attrs, input.span())?;
+
+    let mut bounded = input.generics.clone();
+    let predicates = match attrs.bound {
+        Some(bound) => bound.predicates()?,
+        None => infer_bounds(&input)?,
+    };
+    bounded.make_where_clause().predicates.extend(predicates);
+
+    let target = Target {
+        ident: &input.ident,
+        serde: Container::parse(&input.ident, &input.attrs)?,
+        generics: &input.generics,
+        bounded,
+    };
+
+    let body = match &input.data {
+        syn::Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input,
+                "Figa cannot be derived for unions",
+            ))
+        }
+        syn::Data::Struct(data) => derive_struct(&target, &data.fields)?,
+        syn::Data::Enum(data) => derive_enum(&target, data)?,
+    };
+
+    let ty = target.ty();
+    let validate = derive_validate(&target, &input.data, attrs.validate, &attrs.validate_on)?;
+    let constraints = derive_constraints(&target, &input.data)?;
+    let flat_fields = derive_flat_fields(&target, &input.data)?;
+    let default = derive_default(&target, &input, attrs.default)?;
+
+    let mut generics = target.bounded.clone();
+    let body = match attrs.validate_on {
+        Some(ValidateOn {
+            point: ValidationPoint::Update(_),
+            ..
+        }) => {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote!(#ty: figa::Validate));
+
+            quote::quote! {
+                let result: figa::private::Result<(), __D::Error> = { #body };
+                result?;
+                figa::private::validated(self)
+            }
+        }
+        _ => body,
+    };
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let impls = quote::quote! {
+        impl #impl_generics figa::Figa for #ty #where_clause {
+            fn update<'de, __D>(&mut self, deserializer: __D) -> figa::private::Result<(), __D::Error>
+            where
+                __D: figa::private::Deserializer<'de>,
+            {
+                #body
+            }
+        }
+
+
+        #validate
+
+        #constraints
+
+        #flat_fields
+
+        #default
+    };
+
+    // Generated code refers to `figa` crate by name.
+    // With `#[figa(crate = "...")]` the name is shadowed in the scope of the impls.
+    match attrs.krate {
+        None => Ok(impls),
+        Some(krate) => {
+            let path = krate.path.parse::<syn::Path>()?;
+            Ok(quote::quote! {
+                const _: () = {
+                    use #path as figa;
+
+                    #impls
+                };
+            })
+        }
+    }
+}
+
+/// Implements `Default` for struct with `#[figa(default)]`.
+/// Fields take values from `#[figa(default = expr)]` or their own `Default` implementation.
+fn derive_default(
+    target: &Target,
+    input: &syn::DeriveInput,
+    default: Option<default>,
+) -> syn::Result<Option<TokenStream>> {
+    let fields = match &input.data {
+        syn::Data::Struct(data) => &data.fields,
+        syn::Data::Enum(data) => {
+            if let Some(default) = default {
+                return Err(syn::Error::new(
+                    default.span,
+                    "generated `Default` requires struct",
+                ));
+            }
+            for variant in &data.variants {
+                for field in &variant.fields {
+                    if let Some(value) = Strategy::parse(field)?.default {
+                        return Err(syn::Error::new_spanned(
+                            value,
+                            "`default` is not supported on enum variant field",
+                        ));
+                    }
+                }
+            }
+            return Ok(None);
+        }
+        syn::Data::Union(_) => return Ok(None),
+    };
+
+    let type_params = target
+        .generics
+        .type_params()
+        .map(|param| &param.ident)
+        .collect::<Vec<_>>();
+
+    let mut generics = target.generics.clone();
+    let mut values = Vec::new();
+    for field in fields {
+        let strategy = Strategy::parse(field)?;
+
+        let value = match strategy.default {
+            Some(value) => {
+                if default.is_none() {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        "`default` on field requires `#[figa(default)]` on the type",
+                    ));
+                }
+                value.to_token_stream()
+            }
+            None => {
+                let ty = &field.ty;
+                if mentions(ty.to_token_stream(), &type_params) {
+                    generics
+                        .make_where_clause()
+                        .predicates
+                        .push(syn::parse_quote!(#ty: figa::private::DefaultValue));
+                }
+                quote::quote_spanned!(field.span() => figa::private::DefaultValue::default())
+            }
+        };
+        values.push(value);
+    }
+
+    if default.is_none() {
+        return Ok(None);
+    }
+
+    let ident = target.ident;
+    let value = match fields {
+        syn::Fields::Unit => quote::quote!(#ident),
+        syn::Fields::Unnamed(_) => quote::quote!(#ident(#(#values,)*)),
+        syn::Fields::Named(fields) => {
+            let members = fields.named.iter().map(|field| &field.ident);
+            quote::quote!(#ident { #(#members: #values,)* })
+        }
+    };
+
+    let ty = target.ty();
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(Some(quote::quote! {
+        impl #impl_generics figa::private::DefaultValue for #ty #where_clause {
+            fn default() -> Self {
+                #value
+            }
+        }
+    }))
+}
+
+/// Implements `Validate` if the type or any of its fields has validation attributes.
+/// Field validators run first, then the validator of the type itself.
+fn derive_validate(
+    target: &Target,
+    data: &syn::Data,
+    validate: Option<ValidateArg>,
+    validate_on: &Option<ValidateOn>,
+) -> syn::Result<Option<TokenStream>> {
+    let ident = target.ident;
+    let type_params = target
+        .generics
+        .type_params()
+        .map(|param| &param.ident)
+        .collect::<Vec<_>>();
+
+    let mut generics = target.generics.clone();
+    let mut any_field = false;
+
+    let mut validate_fields = |fields: &syn::Fields,
+                               places: &[TokenStream],
+                               rule: RenameRule|
+     -> syn::Result<_> {
+        let mut checks = Vec::new();
+        for (idx, (field, place)) in fields.iter().zip(places).enumerate() {
+            let strategy = Strategy::parse(field)?;
+            let name = Names::field(field, idx, rule)?.name;
+            let ty = &field.ty;
+
+            let mut check = Vec::new();
+            match &strategy.validate {
+                None => {}
+                Some(Validator::Nested) => {
+                    if mentions(ty.to_token_stream(), &type_params) {
+                        generics
+                            .make_where_clause()
+                            .predicates
+                            .push(syn::parse_quote!(#ty: figa::Validate));
+                    }
+
+                    if strategy.flatten {
+                        check.push(quote::quote_spanned! {field.span() =>
+                            figa::private::Validate::validate(#place)?;
+                        });
+                    } else {
+                        check.push(quote::quote_spanned! {field.span() =>
+                            figa::private::validate_nested(#place, #name)?;
+                        });
+                    }
+                }
+                Some(Validator::With(path)) => check.push(quote::quote_spanned! {field.span() =>
+                    figa::private::validate_with(#path(#place), #name)?;
+                }),
+            }
+
+            for constraint in &strategy.constraints {
+                let constraint = constraint.check(place, is_option(ty));
+                check.push(quote::quote_spanned! {field.span() =>
+                    figa::private::validate_with(#constraint, #name)?;
+                });
+            }
+
+            any_field |= !check.is_empty();
+            checks.push(check);
+        }
+        Ok(checks)
+    };
+
+    let fields = match data {
+        syn::Data::Struct(data) => {
+            let places = data
+                .fields
+                .members()
+                .map(|member| quote::quote!(&self.#member))
+                .collect::<Vec<_>>();
+
+            let checks = validate_fields(&data.fields, &places, target.serde.field_rule())?;
+            let checks = checks.into_iter().flatten();
+            quote::quote!(#(#checks)*)
+        }
+        syn::Data::Enum(data) => {
+            let mut arms = Vec::new();
+            for variant in &data.variants {
+                let variant_ident = &variant.ident;
+                let bindings = (0..variant.fields.len())
+                    .map(|idx| quote::format_ident!("__field{}", idx))
+                    .collect::<Vec<_>>();
+                let places = bindings
+                    .iter()
+                    .map(|binding| binding.to_token_stream())
+                    .collect::<Vec<_>>();
+
+                let (_, rule) = Names::variant(variant, &target.serde)?;
+                let checks = validate_fields(&variant.fields, &places, rule)?;
+
+                let patterns = checks
+                    .iter()
+                    .zip(&bindings)
+                    .map(|(check, binding)| {
+                        if check.is_empty() {
+                            quote::quote!(_)
+                        } else {
+                            binding.to_token_stream()
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                let pattern = match &variant.fields {
+                    syn::Fields::Unit => quote::quote!(#ident::#variant_ident),
+                    syn::Fields::Unnamed(_) => {
+                        quote::quote!(#ident::#variant_ident(#(#patterns,)*))
+                    }
+                    syn::Fields::Named(fields) => {
+                        let members = fields.named.iter().map(|field| &field.ident);
+                        quote::quote!(#ident::#variant_ident { #(#members: #patterns,)* })
+                    }
+                };
+
+                let checks = checks.into_iter().flatten();
+                arms.push(quote::quote! {
+                    #pattern => {
+                        #(#checks)*
+                    }
+                });
+            }
+
+            quote::quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        syn::Data::Union(_) => return Ok(None),
+    };
+
+    let validator = validate.map(Validator::try_from).transpose()?;
+    if !any_field && validator.is_none() && validate_on.is_none() {
+        return Ok(None);
+    }
+
+    let container = match validator {
+        None | Some(Validator::Nested) => TokenStream::new(),
+        Some(Validator::With(path)) => quote::quote! {
+            figa::private::validate_with(#path(self), "")?;
+        },
+    };
+
+    let ty = target.ty();
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(Some(quote::quote! {
+        impl #impl_generics figa::Validate for #ty #where_clause {
+            fn validate(&self) -> figa::private::Result<(), figa::ValidationError> {
+                #fields
+                #container
+                figa::private::Ok(())
+            }
+        }
+    }))
+}
+
+/// Implements `Constraints` for types with constraint attributes on fields.
+/// Generates `FlatFields` implementation for struct with named fields,
+/// so that the struct flattened into another one takes only its own keys,
+/// including keys of its own flattened fields.
+fn derive_flat_fields(target: &Target, data: &syn::Data) -> syn::Result<Option<TokenStream>> {
+    let fields = match data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => fields,
+        _ => return Ok(None),
+    };
+
+    let rule = target.serde.field_rule();
+    let mut aliases = Vec::new();
+    let mut filters = Vec::new();
+    for (idx, field) in fields.named.iter().enumerate() {
+        let strategy = Strategy::parse(field)?;
+        if strategy.skip {
+            continue;
+        }
+        if strategy.flatten {
+            filters.push(flat_filter(&field.ty));
+        } else {
+            aliases.extend(Names::field(field, idx, rule)?.all);
+        }
+    }
+
+    let ty = target.ty();
+    let (impl_generics, _, where_clause) = target.generics.split_for_impl();
+
+    Ok(Some(quote::quote! {
+        impl #impl_generics figa::private::FlatFields for #ty #where_clause {
+            fn is_field(key: &figa::private::str) -> bool {
+                false
+                    #(|| key == #aliases)*
+                    #(|| #filters.map_or(true, |filter| filter(key)))*
+            }
+        }
+    }))
+}
+
+fn derive_constraints(target: &Target, data: &syn::Data) -> syn::Result<Option<TokenStream>> {
+    let mut constraints = Vec::new();
+    let mut describe_fields =
+        |variant: TokenStream, fields: &syn::Fields, rule: RenameRule| -> syn::Result<()> {
+            for (idx, field) in fields.iter().enumerate() {
+                let strategy = Strategy::parse(field)?;
+                if strategy.constraints.is_empty() {
+                    continue;
+                }
+                let name = Names::field(field, idx, rule)?.name;
+                let ty = option_inner(&field.ty).unwrap_or(&field.ty);
+                for constraint in &strategy.constraints {
+                    let constraint = constraint.describe(ty);
+                    constraints.push(quote::quote! {
+                        figa::private::field_constraint(#variant, #name, #constraint)
+                    });
+                }
+            }
+            Ok(())
+        };
+
+    match data {
+        syn::Data::Struct(data) => describe_fields(
+            quote::quote!(figa::private::None),
+            &data.fields,
+            target.serde.field_rule(),
+        )?,
+        syn::Data::Enum(data) => {
+            for variant in &data.variants {
+                let (names, rule) = Names::variant(variant, &target.serde)?;
+                let name = names.name;
+                describe_fields(
+                    quote::quote!(figa::private::Some(#name)),
+                    &variant.fields,
+                    rule,
+                )?;
+            }
+        }
+        syn::Data::Union(_) => {}
+    }
+
+    if constraints.is_empty() {
+        return Ok(None);
+    }
+
+    let ty = target.ty();
+    let (impl_generics, _, where_clause) = target.generics.split_for_impl();
+
+    Ok(Some(quote::quote! {
+        impl #impl_generics figa::Constraints for #ty #where_clause {
+            fn constraints() -> figa::private::Vec<figa::FieldConstraint> {
+                figa::private::Vec::from([#(#constraints,)*])
+            }
+        }
+    }))
+}
+
+/// Infers bounds for generic parameters from strategies of the fields.
+/// Enums also require `DeserializeOwned` to replace the value with another variant.
+fn infer_bounds(input: &syn::DeriveInput) -> syn::Result<Vec<syn::WherePredicate>> {
+    let type_params = input
+        .generics
+        .type_params()
+        .map(|param| &param.ident)
+        .collect::<Vec<_>>();
+
+    let mut bounds = Vec::new();
+    let mut add_field_bounds = |fields: &syn::Fields| -> syn::Result<()> {
+        for field in fields {
+            bounds.extend(Strategy::parse(field)?.bounds(field, &type_params));
+        }
+        Ok(())
+    };
+
+    match &input.data {
+        syn::Data::Struct(data) => add_field_bounds(&data.fields)?,
+        syn::Data::Enum(data) => {
+            for variant in &data.variants {
+                add_field_bounds(&variant.fields)?;
+            }
+
+            if !input.generics.params.is_empty() {
+                let ident = &input.ident;
+                let (_, ty_generics, _) = input.generics.split_for_impl();
+                bounds
+                    .push(syn::parse_quote!(#ident #ty_generics: figa::private::DeserializeOwned));
+
+                // Untagged enum is updated on a copy, see `derive_enum`.
+                if let Representation::Untagged = Container::parse(ident, &input.attrs)?.repr {
+                    bounds.push(syn::parse_quote!(#ident #ty_generics: figa::private::Clone));
+                }
+            }
+        }
+        syn::Data::Union(_) => {}
+    }
+
+    Ok(bounds)
+}
+
+fn derive_struct(target: &Target, fields: &syn::Fields) -> syn::Result<TokenStream> {
+    let ident_lit = &target.serde.name;
+    let ty = target.ty();
+
+    let places = fields
+        .members()
+        .map(|member| quote::quote!(me.#member))
+        .collect::<Vec<_>>();
+
+    let updates = FieldUpdates::new(fields, &places, target.serde.field_rule())?;
+    let bind = quote::quote!(let me = self.0;);
+    let visit_seq = updates.visit_seq(&bind);
+
+    match fields {
+        syn::Fields::Unit => Ok(quote::quote! {
+            deserializer.deserialize_unit_struct(#ident_lit, figa::private::UnitStructVisitor)
+        }),
+        syn::Fields::Named(_) => {
+            let field_ident = updates.field_ident();
+            let visit_map = updates.visit_map(&bind);
+            let deserialize = if updates.has_flatten() {
+                quote::quote!(deserializer.deserialize_map(Visitor(self))?;)
+            } else {
+                let fields = updates.fields();
+                quote::quote!(deserializer.deserialize_struct(#ident_lit, #fields, Visitor(self))?;)
+            };
+
+            let visitor = target.visitor(
+                quote::quote!((&'__a mut #ty)),
+                true,
+                quote::quote! {
+                    fn expecting(&self, formatter: &mut figa::private::Formatter) -> figa::private::FmtResult {
+                        formatter.write_str("struct")
+                    }
+
+                    fn visit_unit<__E>(self) -> figa::private::Result<(), __E> {
+                        figa::private::Ok(())
+                    }
+
+                    #visit_seq
+
+                    #visit_map
+                },
+            );
+
+            Ok(quote::quote! {
+                #field_ident
+
+                #visitor
+
+                #deserialize
+
+                figa::private::Ok(())
+            })
+        }
+        syn::Fields::Unnamed(_) => {
+            let fields_count = updates.seq_len();
+
+            let visitor = target.visitor(
+                quote::quote!((&'__a mut #ty)),
+                true,
+                quote::quote! {
+                    fn expecting(&self, formatter: &mut figa::private::Formatter) -> figa::private::FmtResult {
+                        formatter.write_str("struct")
+                    }
+
+                    fn visit_unit<__E>(self) -> figa::private::Result<(), __E> {
+                        figa::private::Ok(())
+                    }
+
+                    #visit_seq
+                },
+            );
+
+            Ok(quote::quote! {
+                #visitor
+
+                deserializer.deserialize_tuple_struct(#ident_lit, #fields_count, Visitor(self))?;
+
+                figa::private::Ok(())
+            })
+        }
+    }
+}
+
+/// Generates update for enum.
+/// Fields of the active variant are updated if next layer names the same variant,
+/// otherwise value is replaced with freshly deserialized one.
+///
+/// Internally tagged, adjacently tagged and untagged enums are buffered first
+/// to find out which variant the next layer names.
+/// Missing tag means the active variant.
+fn derive_enum(target: &Target, data: &syn::DataEnum) -> syn::Result<TokenStream> {
+    let ident = target.ident;
+    let ident_lit = &target.serde.name;
+    let ty = target.ty();
+
+    let variant_idents = data
+        .variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
+
+    let variant_names = data
+        .variants
+        .iter()
+        .map(|variant| Names::variant(variant, &target.serde))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let variant_lits = variant_names
+        .iter()
+        .map(|(names, _)| &names.name)
+        .collect::<Vec<_>>();
+
+    let variant_aliases = variant_names
+        .iter()
+        .map(|(names, _)| &names.all)
+        .collect::<Vec<_>>();
+
+    let variant_arms = data
+        .variants
+        .iter()
+        .zip(&variant_names)
+        .map(|(variant, (_, rule))| derive_variant_arm(target, variant, *rule))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let expecting = syn::LitStr::new(&format!("enum {}", ident), ident.span());
+
+    let variant_indices = 0..data.variants.len();
+
+    let (replace, update) = match &target.serde.repr {
+        Representation::External => (
+            quote::quote! {
+                *self.0 = <#ty as figa::private::Deserialize>::deserialize(
+                    figa::private::VariantDeserializer::new(VARIANTS[variant as usize], access),
+                )?;
+                figa::private::Ok(())
+            },
+            quote::quote! {
+                deserializer.deserialize_enum(#ident_lit, VARIANTS, Visitor(self))?;
+            },
+        ),
+        Representation::Internal { tag } => (
+            quote::quote!(figa::private::unreachable!()),
+            tagged_update(
+                target,
+                tag,
+                quote::quote! {
+                    figa::private::take_entry(&mut content, #tag);
+                    figa::private::ContentEnumAccess::internal(variant, content)
+                },
+            ),
+        ),
+        Representation::Adjacent { tag, content } => (
+            quote::quote!(figa::private::unreachable!()),
+            tagged_update(
+                target,
+                tag,
+                quote::quote! {
+                    let value = figa::private::take_entry(&mut content, #content);
+                    figa::private::ContentEnumAccess::new(variant, value)
+                },
+            ),
+        ),
+        Representation::Untagged => (
+            quote::quote!(figa::private::unreachable!()),
+            // Active variant is updated if the layer fits it,
+            // otherwise the value is replaced with the variant the layer deserializes as.
+            // The attempt is made on a copy, so the value is unchanged if neither fits.
+            quote::quote! {
+                let content = <figa::private::Content as figa::private::Deserialize>::deserialize(deserializer)?;
+                let variant = figa::private::Content::Str(VARIANTS[active_variant(self)]);
+                let mut updated = figa::private::Clone::clone(&*self);
+                let result = figa::private::Visitor::visit_enum(
+                    Visitor(&mut updated),
+                    figa::private::ContentEnumAccess::<__D::Error>::new(variant, figa::private::Some(content.clone())),
+                );
+
+                match result {
+                    figa::private::Ok(()) => *self = updated,
+                    figa::private::Err(err) => match <#ty as figa::private::Deserialize>::deserialize(
+                        figa::private::ContentDeserializer::<__D::Error>::new(content),
+                    ) {
+                        figa::private::Ok(fresh) => *self = fresh,
+                        figa::private::Err(_) => return figa::private::Err(err),
+                    },
+                }
+            },
+        ),
+    };
+
+    let visitor = target.visitor(
+        quote::quote!((&'__a mut #ty)),
+        true,
+        quote::quote! {
+            fn expecting(&self, formatter: &mut figa::private::Formatter) -> figa::private::FmtResult {
+                formatter.write_str(#expecting)
+            }
+
+            fn visit_enum<__A>(self, data: __A) -> figa::private::Result<(), __A::Error>
+            where
+                __A: figa::private::EnumAccess<'de>,
+            {
+                let (variant, access) = data.variant::<VariantIdent>()?;
+                match (variant, &mut *self.0) {
+                    #(
+                        #variant_arms
+                    )*
+                    #[allow(unreachable_patterns)]
+                    (variant, _) => {
+                        #replace
+                    }
+                }
+            }
+        },
+    );
+
+    let (impl_generics, _, where_clause) = target.generics.split_for_impl();
+
+    Ok(quote::quote! {
+        #[allow(non_camel_case_types)]
+        enum VariantIdent {
+            #(
+                #variant_idents,
+            )*
+        }
+
+        const VARIANTS: &[&figa::private::str] = &[#(#variant_lits,)*];
+
+        struct VariantIdentVisitor;
+
+        impl<'de> figa::private::Visitor<'de> for VariantIdentVisitor {
+            type Value = VariantIdent;
+
+            fn expecting(&self, formatter: &mut figa::private::Formatter) -> figa::private::FmtResult {
+                formatter.write_str("variant identifier")
+            }
+
+            fn visit_str<E>(self, value: &figa::private::str) -> figa::private::Result<VariantIdent, E>
+            where
+                E: figa::private::DeError,
+            {
+                match value {
+                    #(
+                        #(#variant_aliases)|* => figa::private::Ok(VariantIdent::#variant_idents),
+                    )*
+                    _ => figa::private::Err(figa::private::DeError::unknown_variant(value, VARIANTS)),
+                }
+            }
+        }
+
+        impl<'de> figa::private::Deserialize<'de> for VariantIdent {
+            fn deserialize<__D>(deserializer: __D) -> figa::private::Result<Self, __D::Error>
+            where
+                __D: figa::private::Deserializer<'de>,
+            {
+                deserializer.deserialize_identifier(VariantIdentVisitor)
+            }
+        }
+
+        #visitor
+
+        #[allow(dead_code)]
+        fn active_variant #impl_generics (value: &#ty) -> usize #where_clause {
+            match *value {
+                #(
+                    #ident::#variant_idents { .. } => #variant_indices,
+                )*
+            }
+        }
+
+        #update
+
+        figa::private::Ok(())
+    })
+}
+
+/// Generates update for enum with a tag entry.
+/// `access` builds `EnumAccess` for the `variant` from the buffered map.
+fn tagged_update(target: &Target, tag: &syn::LitStr, access: TokenStream) -> TokenStream {
+    let ty = target.ty();
+
+    quote::quote! {
+        let mut content = <figa::private::Content as figa::private::Deserialize>::deserialize(deserializer)?;
+        let active = active_variant(self);
+
+        let variant = match figa::private::find_entry(&content, #tag) {
+            figa::private::None => active,
+            figa::private::Some(tag) => {
+                <VariantIdent as figa::private::Deserialize>::deserialize(
+                    figa::private::ContentDeserializer::<__D::Error>::new(tag.clone()),
+                )? as usize
+            }
+        };
+
+        if variant == active {
+            let variant = figa::private::Content::Str(VARIANTS[variant]);
+            let access = { #access };
+            figa::private::Visitor::visit_enum(Visitor(self), access)?;
+        } else {
+            *self = <#ty as figa::private::Deserialize>::deserialize(
+                figa::private::ContentDeserializer::<__D::Error>::new(content),
+            )?;
+        }
+    }
+}
+
+/// Generates match arm that updates fields of the variant when it is already active.
+/// `rule` renames fields of the variant.
+fn derive_variant_arm(
+    target: &Target,
+    variant: &syn::Variant,
+    rule: RenameRule,
+) -> syn::Result<TokenStream> {
+    let ident = target.ident;
+    let ty = target.ty();
+    let variant_ident = &variant.ident;
+
+    let all_bindings = (0..variant.fields.len())
+        .map(|idx| quote::format_ident!("__field{}", idx))
+        .collect::<Vec<_>>();
+
+    let places = all_bindings
+        .iter()
+        .map(|binding| quote::quote!((*#binding)))
+        .collect::<Vec<_>>();
+
+    // Skipped fields are neither bound nor passed to the visitor.
+    let mut bindings = Vec::new();
+    let mut patterns = Vec::new();
+    let mut types = Vec::new();
+    for (field, binding) in variant.fields.iter().zip(&all_bindings) {
+        if Strategy::parse(field)?.skip {
+            patterns.push(quote::quote!(_));
+        } else {
+            bindings.push(binding);
+            patterns.push(binding.to_token_stream());
+            types.push(&field.ty);
+        }
+    }
+
+    let pattern = match &variant.fields {
+        syn::Fields::Unit => {
+            return Ok(quote::quote! {
+                (VariantIdent::#variant_ident, #ident::#variant_ident) => figa::private::VariantAccess::unit_variant(access),
+            })
+        }
+        syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            let field = &fields.unnamed[0];
+            let strategy = Strategy::parse(field)?;
+            if strategy.skip {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "`skip` is not supported on newtype variant field",
+                ));
+            }
+            let update = strategy.update(
+                field,
+                &places[0],
+                "0",
+                quote::quote!(access.newtype_variant_seed),
+            );
+
+            return Ok(quote::quote! {
+                (VariantIdent::#variant_ident, #ident::#variant_ident(__field0)) => {
+                    use figa::private::VariantAccess as _;
+                    #update
+                    figa::private::Ok(())
+                }
+            });
+        }
+        syn::Fields::Unnamed(_) => quote::quote!(#ident::#variant_ident(#(#patterns,)*)),
+        syn::Fields::Named(fields) => {
+            let members = fields.named.iter().map(|field| &field.ident);
+            quote::quote!(#ident::#variant_ident { #(#members: #patterns,)* })
+        }
+    };
+
+    let updates = FieldUpdates::new(&variant.fields, &places, rule)?;
+    let bind = quote::quote!(let Visitor { #(#bindings,)* .. } = self;);
+    let visit_seq = updates.visit_seq(&bind);
+
+    let (items, visit_map, access, expecting) = match &variant.fields {
+        syn::Fields::Named(_) => {
+            let fields = updates.fields();
+            (
+                updates.field_ident(),
+                updates.visit_map(&bind),
+                quote::quote!(figa::private::VariantAccess::struct_variant(access, #fields, visitor)),
+                format!("struct variant {}::{}", ident, variant_ident),
+            )
+        }
+        _ => {
+            let fields_count = updates.seq_len();
+            (
+                TokenStream::new(),
+                TokenStream::new(),
+                quote::quote!(figa::private::VariantAccess::tuple_variant(access, #fields_count, visitor)),
+                format!("tuple variant {}::{}", ident, variant_ident),
+            )
+        }
+    };
+
+    let expecting = syn::LitStr::new(&expecting, variant_ident.span());
+
+    let visitor = target.visitor(
+        quote::quote! {
+            {
+                #(
+                    #bindings: &'__a mut #types,
+                )*
+                __marker: figa::private::PhantomData<&'__a mut #ty>,
+            }
+        },
+        false,
+        quote::quote! {
+            fn expecting(&self, formatter: &mut figa::private::Formatter) -> figa::private::FmtResult {
+                formatter.write_str(#expecting)
+            }
+
+            #visit_seq
+
+            #visit_map
+        },
+    );
+
+    Ok(quote::quote! {
+        (VariantIdent::#variant_ident, #pattern) => {
+            #items
+
+            #visitor
+
+            let visitor = Visitor { #(#bindings,)* __marker: figa::private::PhantomData };
+            #access
+        }
+    })
 }
@@ -1,6 +1,7 @@
 use proc_macro::TokenStream;
 
 mod figa;
+mod serde_attrs;
 
 #[proc_macro_derive(Figa, attributes(figa))]
 pub fn derive_figa(input: TokenStream) -> TokenStream {
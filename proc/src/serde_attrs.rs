@@ -0,0 +1,290 @@
+//! Parsing of `#[serde(...)]` attributes that affect how input is laid out.
+//! Unknown attributes are left for `serde` to handle.
+
+use syn::{ext::IdentExt, spanned::Spanned};
+
+/// Enum representation selected with `#[serde(tag, content, untagged)]`.
+pub enum Representation {
+    External,
+    Internal {
+        tag: syn::LitStr,
+    },
+    Adjacent {
+        tag: syn::LitStr,
+        content: syn::LitStr,
+    },
+    Untagged,
+}
+
+/// Rule from `#[serde(rename_all = "...")]`.
+#[derive(Clone, Copy)]
+pub enum RenameRule {
+    None,
+    LowerCase,
+    UpperCase,
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    ScreamingKebabCase,
+}
+
+impl RenameRule {
+    fn parse(lit: &syn::LitStr) -> syn::Result<Self> {
+        match &*lit.value() {
+            "lowercase" => Ok(RenameRule::LowerCase),
+            "UPPERCASE" => Ok(RenameRule::UpperCase),
+            "PascalCase" => Ok(RenameRule::PascalCase),
+            "camelCase" => Ok(RenameRule::CamelCase),
+            "snake_case" => Ok(RenameRule::SnakeCase),
+            "SCREAMING_SNAKE_CASE" => Ok(RenameRule::ScreamingSnakeCase),
+            "kebab-case" => Ok(RenameRule::KebabCase),
+            "SCREAMING-KEBAB-CASE" => Ok(RenameRule::ScreamingKebabCase),
+            _ => Err(syn::Error::new(lit.span(), "unknown rename rule")),
+        }
+    }
+
+    /// Applies rule to variant name, which is expected to be in `PascalCase`.
+    fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            RenameRule::None | RenameRule::PascalCase => variant.to_owned(),
+            RenameRule::LowerCase => variant.to_ascii_lowercase(),
+            RenameRule::UpperCase => variant.to_ascii_uppercase(),
+            RenameRule::CamelCase => {
+                let mut chars = variant.chars();
+                match chars.next() {
+                    None => String::new(),
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                }
+            }
+            RenameRule::SnakeCase => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            RenameRule::ScreamingSnakeCase => RenameRule::SnakeCase
+                .apply_to_variant(variant)
+                .to_ascii_uppercase(),
+            RenameRule::KebabCase => RenameRule::SnakeCase
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+            RenameRule::ScreamingKebabCase => RenameRule::ScreamingSnakeCase
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+
+    /// Applies rule to field name, which is expected to be in `snake_case`.
+    fn apply_to_field(self, field: &str) -> String {
+        match self {
+            RenameRule::None | RenameRule::LowerCase | RenameRule::SnakeCase => field.to_owned(),
+            RenameRule::UpperCase | RenameRule::ScreamingSnakeCase => field.to_ascii_uppercase(),
+            RenameRule::PascalCase => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            RenameRule::CamelCase => {
+                let pascal = RenameRule::PascalCase.apply_to_field(field);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    None => String::new(),
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                }
+            }
+            RenameRule::KebabCase => field.replace('_', "-"),
+            RenameRule::ScreamingKebabCase => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+/// Container attributes.
+pub struct Container {
+    /// Name of the type as passed to `Deserializer`.
+    pub name: syn::LitStr,
+    pub repr: Representation,
+    rename_all: RenameRule,
+    rename_all_fields: RenameRule,
+}
+
+impl Container {
+    pub fn parse(ident: &syn::Ident, attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut rename = None;
+        let mut rename_all = RenameRule::None;
+        let mut rename_all_fields = RenameRule::None;
+        let mut tag = None;
+        let mut content = None;
+        let mut untagged = false;
+
+        for attr in serde_attrs(attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    rename = deserialize_value(&meta)?.or(rename.take());
+                } else if meta.path.is_ident("rename_all") {
+                    if let Some(rule) = deserialize_value(&meta)? {
+                        rename_all = RenameRule::parse(&rule)?;
+                    }
+                } else if meta.path.is_ident("rename_all_fields") {
+                    if let Some(rule) = deserialize_value(&meta)? {
+                        rename_all_fields = RenameRule::parse(&rule)?;
+                    }
+                } else if meta.path.is_ident("tag") {
+                    tag = Some(meta.value()?.parse::<syn::LitStr>()?);
+                } else if meta.path.is_ident("content") {
+                    content = Some(meta.value()?.parse::<syn::LitStr>()?);
+                } else if meta.path.is_ident("untagged") {
+                    untagged = true;
+                } else {
+                    skip_meta(&meta)?;
+                }
+                Ok(())
+            })?;
+        }
+
+        let repr = match (untagged, tag, content) {
+            (true, _, _) => Representation::Untagged,
+            (false, Some(tag), Some(content)) => Representation::Adjacent { tag, content },
+            (false, Some(tag), None) => Representation::Internal { tag },
+            (false, None, _) => Representation::External,
+        };
+
+        Ok(Container {
+            name: rename
+                .unwrap_or_else(|| syn::LitStr::new(&ident.unraw().to_string(), ident.span())),
+            repr,
+            rename_all,
+            rename_all_fields,
+        })
+    }
+
+    /// Rule for fields of the struct.
+    pub fn field_rule(&self) -> RenameRule {
+        self.rename_all
+    }
+}
+
+/// Names a field or variant is deserialized from.
+pub struct Names {
+    /// Primary name.
+    pub name: syn::LitStr,
+    /// Primary name and aliases.
+    pub all: Vec<syn::LitStr>,
+}
+
+impl Names {
+    pub fn field(field: &syn::Field, idx: usize, rule: RenameRule) -> syn::Result<Self> {
+        let name = match &field.ident {
+            Some(ident) => rule.apply_to_field(&ident.unraw().to_string()),
+            None => idx.to_string(),
+        };
+
+        Names::parse(&field.attrs, syn::LitStr::new(&name, field.span()), |_| {
+            Ok(false)
+        })
+    }
+
+    /// Parses names of the variant and rule for its fields.
+    pub fn variant(
+        variant: &syn::Variant,
+        container: &Container,
+    ) -> syn::Result<(Self, RenameRule)> {
+        let name = container
+            .rename_all
+            .apply_to_variant(&variant.ident.unraw().to_string());
+
+        let mut rule = container.rename_all_fields;
+        let names = Names::parse(
+            &variant.attrs,
+            syn::LitStr::new(&name, variant.ident.span()),
+            |meta| {
+                if meta.path.is_ident("rename_all") {
+                    if let Some(lit) = deserialize_value(meta)? {
+                        rule = RenameRule::parse(&lit)?;
+                    }
+                    return Ok(true);
+                }
+                Ok(false)
+            },
+        )?;
+
+        Ok((names, rule))
+    }
+
+    /// Parses `rename` and `alias` attributes.
+    /// `other` handles other attributes and returns `true` if it did.
+    fn parse(
+        attrs: &[syn::Attribute],
+        default: syn::LitStr,
+        mut other: impl FnMut(&syn::meta::ParseNestedMeta) -> syn::Result<bool>,
+    ) -> syn::Result<Self> {
+        let mut name = None;
+        let mut aliases = Vec::new();
+
+        for attr in serde_attrs(attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    name = deserialize_value(&meta)?.or(name.take());
+                } else if meta.path.is_ident("alias") {
+                    aliases.push(meta.value()?.parse::<syn::LitStr>()?);
+                } else if !other(&meta)? {
+                    skip_meta(&meta)?;
+                }
+                Ok(())
+            })?;
+        }
+
+        let name = name.unwrap_or(default);
+        let mut all = vec![name.clone()];
+        all.extend(aliases);
+
+        Ok(Names { name, all })
+    }
+}
+
+fn serde_attrs(attrs: &[syn::Attribute]) -> impl Iterator<Item = &syn::Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("serde"))
+}
+
+/// Parses value of `name = "..."` or `name(deserialize = "...")`.
+fn deserialize_value(meta: &syn::meta::ParseNestedMeta) -> syn::Result<Option<syn::LitStr>> {
+    if meta.input.peek(syn::Token![=]) {
+        return Ok(Some(meta.value()?.parse()?));
+    }
+
+    let mut value = None;
+    meta.parse_nested_meta(|meta| {
+        if meta.path.is_ident("deserialize") {
+            value = Some(meta.value()?.parse()?);
+        } else {
+            skip_meta(&meta)?;
+        }
+        Ok(())
+    })?;
+    Ok(value)
+}
+
+/// Skips value of unknown nested meta item.
+fn skip_meta(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.input.parse::<proc_macro2::TokenTree>()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use serde::{
+    de::{DeserializeSeed, Error},
+    Deserialize, Deserializer,
+};
+
+/// Keeps the greatest of existing and incoming values.
+pub struct Max<'a, F>(pub &'a mut F);
+
+/// Keeps the least of existing and incoming values.
+pub struct Min<'a, F>(pub &'a mut F);
+
+/// Adds incoming value to the existing one.
+/// Fails on overflow.
+pub struct Sum<'a, F>(pub &'a mut F);
+
+/// Sets flag if incoming or existing value is set.
+pub struct Any<'a, F>(pub &'a mut F);
+
+/// Sets flag only if both incoming and existing values are set.
+pub struct All<'a, F>(pub &'a mut F);
+
+macro_rules! aggregate_int {
+    ($($types:ty)*) => {
+        $(
+            impl<'de> DeserializeSeed<'de> for Max<'_, $types> {
+                type Value = ();
+
+                #[inline]
+                fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    let value = <$types>::deserialize(deserializer)?;
+                    *self.0 = (*self.0).max(value);
+                    Ok(())
+                }
+            }
+
+            impl<'de> DeserializeSeed<'de> for Min<'_, $types> {
+                type Value = ();
+
+                #[inline]
+                fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    let value = <$types>::deserialize(deserializer)?;
+                    *self.0 = (*self.0).min(value);
+                    Ok(())
+                }
+            }
+
+            impl<'de> DeserializeSeed<'de> for Sum<'_, $types> {
+                type Value = ();
+
+                #[inline]
+                fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    let value = <$types>::deserialize(deserializer)?;
+                    match self.0.checked_add(value) {
+                        Some(sum) => *self.0 = sum,
+                        None => return Err(D::Error::custom("sum overflow")),
+                    }
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! aggregate_float {
+    ($($types:ty)*) => {
+        $(
+            impl<'de> DeserializeSeed<'de> for Max<'_, $types> {
+                type Value = ();
+
+                #[inline]
+                fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    let value = <$types>::deserialize(deserializer)?;
+                    *self.0 = self.0.max(value);
+                    Ok(())
+                }
+            }
+
+            impl<'de> DeserializeSeed<'de> for Min<'_, $types> {
+                type Value = ();
+
+                #[inline]
+                fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    let value = <$types>::deserialize(deserializer)?;
+                    *self.0 = self.0.min(value);
+                    Ok(())
+                }
+            }
+
+            impl<'de> DeserializeSeed<'de> for Sum<'_, $types> {
+                type Value = ();
+
+                #[inline]
+                fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    let value = <$types>::deserialize(deserializer)?;
+                    let sum = *self.0 + value;
+                    if sum.is_infinite() && self.0.is_finite() && value.is_finite() {
+                        return Err(D::Error::custom("sum overflow"));
+                    }
+                    *self.0 = sum;
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+aggregate_int!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+aggregate_float!(f32 f64);
+
+impl<'de> DeserializeSeed<'de> for Any<'_, bool> {
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        *self.0 |= bool::deserialize(deserializer)?;
+        Ok(())
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for All<'_, bool> {
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        *self.0 &= bool::deserialize(deserializer)?;
+        Ok(())
+    }
+}
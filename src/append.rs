@@ -1,6 +1,11 @@
 use core::fmt;
 
-#[cfg(any(feature = "std", feature = "hashbrown"))]
+#[cfg(any(
+    feature = "std",
+    feature = "hashbrown",
+    feature = "indexmap",
+    feature = "heapless"
+))]
 use core::hash::{BuildHasher, Hash};
 
 #[cfg(feature = "alloc")]
@@ -87,6 +92,45 @@ impl<'de> Visitor<'de> for Append<'_, String> {
     }
 }
 
+/// Appends string with separator in between.
+/// Separator is inserted only when both existing and appended strings are not empty.
+#[cfg(feature = "alloc")]
+pub struct AppendSeparated<'a>(pub &'a mut String, pub &'a str);
+
+#[cfg(feature = "alloc")]
+impl<'de> DeserializeSeed<'de> for AppendSeparated<'_> {
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Visitor<'de> for AppendSeparated<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("string")
+    }
+
+    #[inline]
+    fn visit_str<E>(self, value: &str) -> Result<(), E>
+    where
+        E: serde::de::Error,
+    {
+        if !self.0.is_empty() && !value.is_empty() {
+            self.0.push_str(self.1);
+        }
+        self.0.push_str(value);
+        Ok(())
+    }
+}
+
 #[cfg(feature = "alloc")]
 impl<'de, F> DeserializeSeed<'de> for Append<'_, Vec<F>>
 where
@@ -126,7 +170,51 @@ where
     }
 }
 
-#[cfg(any(feature = "alloc", feature = "hashbrown"))]
+#[cfg(feature = "alloc")]
+macro_rules! append_seq {
+    ($($seq:ident)::+ <T> => $push:ident $(where $($param:ident : $bound:path),* $(,)?)?) => {
+        impl<'de, T> DeserializeSeed<'de> for Append<'_, $($seq)::+ <T>>
+        where
+            T: DeserializeOwned,
+            $($($param: $bound,)*)?
+        {
+            type Value = ();
+
+            #[inline]
+            fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer.deserialize_seq(self)
+            }
+        }
+
+        impl<'de, T> Visitor<'de> for Append<'_, $($seq)::+ <T>>
+        where
+            T: DeserializeOwned,
+            $($($param: $bound,)*)?
+        {
+            type Value = ();
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("sequence")
+            }
+
+            #[inline]
+            fn visit_seq<X>(self, mut seq: X) -> Result<(), X::Error>
+            where
+                X: serde::de::SeqAccess<'de>,
+            {
+                while let Some(value) = seq.next_element()? {
+                    self.0.$push(value);
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(any(feature = "alloc", feature = "hashbrown", feature = "indexmap"))]
 macro_rules! append_map {
     ($($map:ident)::+ <K, V $(, $tail:ident)*> $(where $($param:ident : $bound:path),* $(,)?)?) => {
         impl<'de, K, V $(, $tail)*> DeserializeSeed<'de> for Append<'_, $($map)::+ <K, V $(, $tail)*>>
@@ -172,7 +260,7 @@ macro_rules! append_map {
     };
 }
 
-#[cfg(any(feature = "alloc", feature = "hashbrown"))]
+#[cfg(any(feature = "alloc", feature = "hashbrown", feature = "indexmap"))]
 macro_rules! append_set {
     ($($set:ident)::+ <T $(, $tail:ident)*> $(where $($param:ident : $bound:path),* $(,)?)?) => {
         impl<'de, T $(, $tail)*> DeserializeSeed<'de> for Append<'_, $($set)::+ <T $(, $tail)*>>
@@ -222,6 +310,15 @@ append_map!(alloc::collections::BTreeMap<K, V> where K: Ord);
 #[cfg(feature = "alloc")]
 append_set!(alloc::collections::BTreeSet<T> where T: Ord);
 
+#[cfg(feature = "alloc")]
+append_seq!(alloc::collections::VecDeque<T> => push_back);
+
+#[cfg(feature = "alloc")]
+append_seq!(alloc::collections::LinkedList<T> => push_back);
+
+#[cfg(feature = "alloc")]
+append_seq!(alloc::collections::BinaryHeap<T> => push where T: Ord);
+
 #[cfg(feature = "std")]
 append_map!(std::collections::HashMap<K, V, S> where K: Eq, K: Hash, S: BuildHasher);
 
@@ -233,3 +330,276 @@ append_map!(hashbrown::HashMap<K, V, S> where K: Eq, K: Hash, S: BuildHasher);
 
 #[cfg(feature = "hashbrown")]
 append_set!(hashbrown::HashSet<T, S> where T: Eq, T: Hash, S: BuildHasher);
+
+#[cfg(feature = "indexmap")]
+append_map!(indexmap::IndexMap<K, V, S> where K: Eq, K: Hash, S: BuildHasher);
+
+#[cfg(feature = "indexmap")]
+append_set!(indexmap::IndexSet<T, S> where T: Eq, T: Hash, S: BuildHasher);
+
+#[cfg(feature = "heapless")]
+impl<'de, T, const N: usize> DeserializeSeed<'de> for Append<'_, heapless::Vec<T, N>>
+where
+    T: DeserializeOwned,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'de, T, const N: usize> Visitor<'de> for Append<'_, heapless::Vec<T, N>>
+where
+    T: DeserializeOwned,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "sequence of at most {} elements", N)
+    }
+
+    /// Elements pushed by the layer are removed if it fails.
+    #[inline]
+    fn visit_seq<A>(self, mut seq: A) -> Result<(), A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let len = self.0.len();
+        loop {
+            match seq.next_element() {
+                Ok(None) => return Ok(()),
+                Ok(Some(value)) => {
+                    if self.0.push(value).is_err() {
+                        self.0.truncate(len);
+                        return Err(serde::de::Error::invalid_length(N + 1, &self));
+                    }
+                }
+                Err(err) => {
+                    self.0.truncate(len);
+                    return Err(err);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'de, const N: usize> DeserializeSeed<'de> for Append<'_, heapless::String<N>> {
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'de, const N: usize> Visitor<'de> for Append<'_, heapless::String<N>> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "string of at most {} bytes", N)
+    }
+
+    #[inline]
+    fn visit_str<E>(self, value: &str) -> Result<(), E>
+    where
+        E: serde::de::Error,
+    {
+        if self.0.push_str(value).is_err() {
+            return Err(E::invalid_length(self.0.len() + value.len(), &self));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'de, K, V, S, const N: usize> DeserializeSeed<'de>
+    for Append<'_, heapless::IndexMap<K, V, S, N>>
+where
+    K: DeserializeOwned + Eq + Hash,
+    V: DeserializeOwned,
+    S: BuildHasher,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'de, K, V, S, const N: usize> Visitor<'de> for Append<'_, heapless::IndexMap<K, V, S, N>>
+where
+    K: DeserializeOwned + Eq + Hash,
+    V: DeserializeOwned,
+    S: BuildHasher,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "map of at most {} entries", N)
+    }
+
+    #[inline]
+    fn visit_map<X>(self, mut map: X) -> Result<(), X::Error>
+    where
+        X: serde::de::MapAccess<'de>,
+    {
+        while let Some((key, value)) = map.next_entry()? {
+            if self.0.insert(key, value).is_err() {
+                return Err(serde::de::Error::invalid_length(N + 1, &self));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'de, K, V, const N: usize> DeserializeSeed<'de> for Append<'_, heapless::LinearMap<K, V, N>>
+where
+    K: DeserializeOwned + Eq,
+    V: DeserializeOwned,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'de, K, V, const N: usize> Visitor<'de> for Append<'_, heapless::LinearMap<K, V, N>>
+where
+    K: DeserializeOwned + Eq,
+    V: DeserializeOwned,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "map of at most {} entries", N)
+    }
+
+    #[inline]
+    fn visit_map<X>(self, mut map: X) -> Result<(), X::Error>
+    where
+        X: serde::de::MapAccess<'de>,
+    {
+        while let Some((key, value)) = map.next_entry()? {
+            if self.0.insert(key, value).is_err() {
+                return Err(serde::de::Error::invalid_length(N + 1, &self));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<'de, A> DeserializeSeed<'de> for Append<'_, smallvec::SmallVec<A>>
+where
+    A: smallvec::Array,
+    A::Item: DeserializeOwned,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<'de, A> Visitor<'de> for Append<'_, smallvec::SmallVec<A>>
+where
+    A: smallvec::Array,
+    A::Item: DeserializeOwned,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("sequence")
+    }
+
+    #[inline]
+    fn visit_seq<X>(self, mut seq: X) -> Result<(), X::Error>
+    where
+        X: serde::de::SeqAccess<'de>,
+    {
+        while let Some(value) = seq.next_element()? {
+            self.0.push(value);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<'de, T, const N: usize> DeserializeSeed<'de> for Append<'_, arrayvec::ArrayVec<T, N>>
+where
+    T: DeserializeOwned,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<'de, T, const N: usize> Visitor<'de> for Append<'_, arrayvec::ArrayVec<T, N>>
+where
+    T: DeserializeOwned,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "sequence of at most {} elements", N)
+    }
+
+    /// Elements pushed by the layer are removed if it fails.
+    #[inline]
+    fn visit_seq<X>(self, mut seq: X) -> Result<(), X::Error>
+    where
+        X: serde::de::SeqAccess<'de>,
+    {
+        let len = self.0.len();
+        loop {
+            match seq.next_element() {
+                Ok(None) => return Ok(()),
+                Ok(Some(value)) => {
+                    if self.0.try_push(value).is_err() {
+                        self.0.truncate(len);
+                        return Err(serde::de::Error::invalid_length(N + 1, &self));
+                    }
+                }
+                Err(err) => {
+                    self.0.truncate(len);
+                    return Err(err);
+                }
+            }
+        }
+    }
+}
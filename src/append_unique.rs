@@ -0,0 +1,109 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "std")]
+use std::collections::{hash_map::RandomState, HashMap};
+
+use alloc::vec::Vec;
+
+use serde::{
+    de::{DeserializeOwned, DeserializeSeed, Visitor},
+    Deserializer,
+};
+
+/// Appends values that are not yet present in the collection.
+/// Preserves order of existing and appended values.
+pub struct AppendUnique<'a, F>(pub &'a mut F);
+
+impl<'de, F> DeserializeSeed<'de> for AppendUnique<'_, Vec<F>>
+where
+    F: DeserializeOwned + PartialEq,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, F> Visitor<'de> for AppendUnique<'_, Vec<F>>
+where
+    F: DeserializeOwned + PartialEq,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("sequence")
+    }
+
+    #[inline]
+    fn visit_seq<A>(self, mut seq: A) -> Result<(), A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        while let Some(value) = seq.next_element()? {
+            if !self.0.contains(&value) {
+                self.0.push(value);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Same as [`AppendUnique`], but finds present values by hash.
+/// Takes linear time in the number of existing and appended values.
+#[cfg(feature = "std")]
+pub struct AppendUniqueHash<'a, F>(pub &'a mut F);
+
+#[cfg(feature = "std")]
+impl<'de, F> DeserializeSeed<'de> for AppendUniqueHash<'_, Vec<F>>
+where
+    F: DeserializeOwned + Hash + Eq,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, F> Visitor<'de> for AppendUniqueHash<'_, Vec<F>>
+where
+    F: DeserializeOwned + Hash + Eq,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("sequence")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<(), A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        // Indices of values by hash, so values need not be cloned into a set.
+        let state = RandomState::new();
+        let mut seen = HashMap::<u64, Vec<usize>>::with_capacity(self.0.len());
+        for (idx, value) in self.0.iter().enumerate() {
+            seen.entry(state.hash_one(value)).or_default().push(idx);
+        }
+
+        while let Some(value) = seq.next_element::<F>()? {
+            let indices = seen.entry(state.hash_one(&value)).or_default();
+            if !indices.iter().any(|&idx| self.0[idx] == value) {
+                indices.push(self.0.len());
+                self.0.push(value);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+use serde::de::Error;
+
+/// What to do when collection exceeds maximum length.
+pub enum Overflow {
+    /// Fail to load the layer.
+    Error,
+
+    /// Keep first elements and drop the rest.
+    KeepFirst,
+
+    /// Keep last elements and drop the rest.
+    /// This makes a sliding window over layers.
+    KeepLast,
+}
+
+/// Collections with length that can be bounded.
+pub trait Bounded {
+    /// Returns length that is compared with the limit.
+    fn length(&self) -> usize;
+
+    /// Keeps at most `len` first elements.
+    fn keep_first(&mut self, len: usize);
+
+    /// Keeps at most `len` last elements.
+    fn keep_last(&mut self, len: usize);
+}
+
+/// Enforces maximum length of the collection after the update.
+pub fn bound<F, E>(value: &mut F, max_len: usize, overflow: Overflow, field: &str) -> Result<(), E>
+where
+    F: Bounded,
+    E: Error,
+{
+    if value.length() <= max_len {
+        return Ok(());
+    }
+
+    match overflow {
+        Overflow::Error => {
+            return Err(E::custom(format_args!(
+                "field `{}` exceeds maximum length of {}",
+                field, max_len
+            )))
+        }
+        Overflow::KeepFirst => value.keep_first(max_len),
+        Overflow::KeepLast => value.keep_last(max_len),
+    }
+    Ok(())
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Bounded for Vec<T> {
+    #[inline]
+    fn length(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn keep_first(&mut self, len: usize) {
+        self.truncate(len);
+    }
+
+    #[inline]
+    fn keep_last(&mut self, len: usize) {
+        if self.len() > len {
+            self.drain(..self.len() - len);
+        }
+    }
+}
+
+/// Length of `String` is measured in bytes.
+/// Truncation never splits a character, so resulting string may be shorter than the limit.
+#[cfg(feature = "alloc")]
+impl Bounded for String {
+    #[inline]
+    fn length(&self) -> usize {
+        self.len()
+    }
+
+    fn keep_first(&mut self, len: usize) {
+        if self.len() > len {
+            let mut end = len;
+            while !self.is_char_boundary(end) {
+                end -= 1;
+            }
+            self.truncate(end);
+        }
+    }
+
+    fn keep_last(&mut self, len: usize) {
+        if self.len() > len {
+            let mut start = self.len() - len;
+            while !self.is_char_boundary(start) {
+                start += 1;
+            }
+            self.drain(..start);
+        }
+    }
+}
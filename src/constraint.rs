@@ -0,0 +1,365 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    format,
+    string::String,
+    vec::Vec,
+};
+use core::{
+    fmt::Debug,
+    ops::{Bound, RangeBounds},
+};
+
+use serde::de::Error;
+
+use crate::ValidationError;
+
+/// Constraints declared on fields of the type
+/// with `#[figa(range = ..)]`, `#[figa(len = ..)]`, `#[figa(pattern = "..")]` and `#[figa(one_of = [..])]`.
+///
+/// Derive macro implements this trait for types with constraint attributes,
+/// so schema generators can export the constraints along with the fields.
+pub trait Constraints {
+    /// Constraints of the fields in declaration order.
+    fn constraints() -> Vec<FieldConstraint>;
+}
+
+/// Constraint declared on a field.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldConstraint {
+    variant: Option<&'static str>,
+    field: &'static str,
+    constraint: Constraint,
+}
+
+impl FieldConstraint {
+    /// Name of the enum variant the field belongs to.
+    /// `None` for struct fields.
+    pub fn variant(&self) -> Option<&'static str> {
+        self.variant
+    }
+
+    /// Name of the field as it appears in the input.
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+
+    /// Constraint of the field.
+    pub fn constraint(&self) -> &Constraint {
+        &self.constraint
+    }
+}
+
+/// Declared constraint of a field value.
+/// Bounds and allowed values are evaluated from the expressions in the attribute.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Constraint {
+    /// `#[figa(range = ..)]`.
+    Range {
+        start: Bound<ConstraintValue>,
+        end: Bound<ConstraintValue>,
+    },
+    /// `#[figa(len = ..)]`.
+    Len {
+        start: Bound<usize>,
+        end: Bound<usize>,
+    },
+    /// `#[figa(pattern = "..")]` with the regular expression.
+    Pattern(&'static str),
+    /// `#[figa(one_of = [..])]` with allowed values.
+    OneOf(Vec<ConstraintValue>),
+}
+
+/// Value of range bound or allowed value in [`Constraint`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConstraintValue {
+    /// Integer value.
+    Int(i128),
+    /// Floating point value.
+    Float(f64),
+    Bool(bool),
+    Char(char),
+    Str(String),
+    /// `Debug` representation of a value of other type,
+    /// or of `u128` value that doesn't fit into `i128`.
+    Other(String),
+}
+
+/// Converts values of primitive types and strings to [`ConstraintValue`].
+pub trait ToConstraintValue {
+    fn to_constraint_value(&self) -> ConstraintValue;
+}
+
+macro_rules! int_constraint_value {
+    ($($ty:ty)*) => {
+        $(
+            impl ToConstraintValue for $ty {
+                #[inline]
+                fn to_constraint_value(&self) -> ConstraintValue {
+                    match i128::try_from(*self) {
+                        Ok(value) => ConstraintValue::Int(value),
+                        Err(_) => ConstraintValue::Other(format!("{:?}", self)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+int_constraint_value!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+impl ToConstraintValue for f32 {
+    #[inline]
+    fn to_constraint_value(&self) -> ConstraintValue {
+        ConstraintValue::Float(f64::from(*self))
+    }
+}
+
+impl ToConstraintValue for f64 {
+    #[inline]
+    fn to_constraint_value(&self) -> ConstraintValue {
+        ConstraintValue::Float(*self)
+    }
+}
+
+impl ToConstraintValue for bool {
+    #[inline]
+    fn to_constraint_value(&self) -> ConstraintValue {
+        ConstraintValue::Bool(*self)
+    }
+}
+
+impl ToConstraintValue for char {
+    #[inline]
+    fn to_constraint_value(&self) -> ConstraintValue {
+        ConstraintValue::Char(*self)
+    }
+}
+
+impl ToConstraintValue for str {
+    #[inline]
+    fn to_constraint_value(&self) -> ConstraintValue {
+        ConstraintValue::Str(self.into())
+    }
+}
+
+impl ToConstraintValue for String {
+    #[inline]
+    fn to_constraint_value(&self) -> ConstraintValue {
+        ConstraintValue::Str(self.clone())
+    }
+}
+
+impl<T> ToConstraintValue for &T
+where
+    T: ToConstraintValue + ?Sized,
+{
+    #[inline]
+    fn to_constraint_value(&self) -> ConstraintValue {
+        (**self).to_constraint_value()
+    }
+}
+
+/// Picks conversion of a value in constraint attribute to [`ConstraintValue`].
+///
+/// Method call `(&ValueProbe(value)).constraint_value()` resolves to [`ValueByConversion`]
+/// if the value implements [`ToConstraintValue`] and to [`ValueByDebug`] otherwise.
+pub struct ValueProbe<'a, T: ?Sized>(pub &'a T);
+
+pub trait ValueByConversion {
+    fn constraint_value(&self) -> ConstraintValue;
+}
+
+impl<T> ValueByConversion for ValueProbe<'_, T>
+where
+    T: ToConstraintValue + ?Sized,
+{
+    #[inline]
+    fn constraint_value(&self) -> ConstraintValue {
+        self.0.to_constraint_value()
+    }
+}
+
+pub trait ValueByDebug {
+    fn constraint_value(&self) -> ConstraintValue;
+}
+
+impl<T> ValueByDebug for &ValueProbe<'_, T>
+where
+    T: Debug + ?Sized,
+{
+    #[inline]
+    fn constraint_value(&self) -> ConstraintValue {
+        ConstraintValue::Other(format!("{:?}", self.0))
+    }
+}
+
+/// Describes constraint declared on a field.
+pub fn field_constraint(
+    variant: Option<&'static str>,
+    field: &'static str,
+    constraint: Constraint,
+) -> FieldConstraint {
+    FieldConstraint {
+        variant,
+        field,
+        constraint,
+    }
+}
+
+/// Allowed values of `#[figa(one_of = [..])]` constraint on field of type `T`.
+/// Infers type of the values the same way [`check_one_of`] does.
+#[inline]
+pub fn one_of_values<T, U>(allowed: &[U]) -> &[U]
+where
+    T: PartialEq<U> + ?Sized,
+{
+    allowed
+}
+
+/// Values with length checked by `#[figa(len = ..)]`.
+pub trait Length {
+    /// Number of elements, or number of bytes for strings.
+    fn length(&self) -> usize;
+}
+
+impl Length for str {
+    #[inline]
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl Length for String {
+    #[inline]
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for [T] {
+    #[inline]
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, const N: usize> Length for [T; N] {
+    #[inline]
+    fn length(&self) -> usize {
+        N
+    }
+}
+
+impl<T> Length for Vec<T> {
+    #[inline]
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for VecDeque<T> {
+    #[inline]
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for BTreeSet<T> {
+    #[inline]
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V> Length for BTreeMap<K, V> {
+    #[inline]
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> Length for std::collections::HashSet<T, S> {
+    #[inline]
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> Length for std::collections::HashMap<K, V, S> {
+    #[inline]
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Checks `#[figa(range = ..)]` constraint.
+pub fn check_range<T, R>(value: &T, range: R) -> Result<(), String>
+where
+    T: PartialOrd + Debug + ?Sized,
+    R: RangeBounds<T> + Debug,
+{
+    if range.contains(value) {
+        Ok(())
+    } else {
+        Err(format!("value {:?} is out of range {:?}", value, range))
+    }
+}
+
+/// Checks `#[figa(len = ..)]` constraint.
+pub fn check_len<T, R>(value: &T, range: R) -> Result<(), String>
+where
+    T: Length + ?Sized,
+    R: RangeBounds<usize> + Debug,
+{
+    let length = value.length();
+    if range.contains(&length) {
+        Ok(())
+    } else {
+        Err(format!("length {} is out of range {:?}", length, range))
+    }
+}
+
+/// Checks `#[figa(one_of = [..])]` constraint.
+pub fn check_one_of<T, U>(value: &T, allowed: &[U]) -> Result<(), String>
+where
+    T: PartialEq<U> + Debug + ?Sized,
+    U: Debug,
+{
+    if allowed.iter().any(|allowed| *value == *allowed) {
+        Ok(())
+    } else {
+        Err(format!("value {:?} is not one of {:?}", value, allowed))
+    }
+}
+
+/// Regular expression compiled on first use.
+#[cfg(feature = "regex")]
+pub type Pattern = std::sync::OnceLock<Result<regex::Regex, regex::Error>>;
+
+/// Checks `#[figa(pattern = "..")]` constraint.
+/// `regex` caches the expression compiled from `pattern`.
+#[cfg(feature = "regex")]
+pub fn check_pattern<T>(value: &T, regex: &Pattern, pattern: &str) -> Result<(), String>
+where
+    T: AsRef<str> + ?Sized,
+{
+    let value = value.as_ref();
+    match regex.get_or_init(|| regex::Regex::new(pattern)) {
+        Err(err) => Err(format!("invalid pattern {:?}: {}", pattern, err)),
+        Ok(regex) if regex.is_match(value) => Ok(()),
+        Ok(_) => Err(format!(
+            "value {:?} does not match pattern {:?}",
+            value, pattern
+        )),
+    }
+}
+
+/// Fails to load the layer if constraint of the updated field is violated.
+pub fn constrained<E>(result: Result<(), String>, field: &str) -> Result<(), E>
+where
+    E: Error,
+{
+    result.map_err(|message| E::custom(ValidationError::new(message).in_field(field)))
+}
@@ -0,0 +1,781 @@
+use core::{fmt, marker::PhantomData};
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use serde::{
+    de::{
+        value::{MapDeserializer, SeqDeserializer},
+        DeserializeSeed, EnumAccess, Error, IntoDeserializer, MapAccess, SeqAccess, Unexpected,
+        VariantAccess, Visitor,
+    },
+    forward_to_deserialize_any, Deserialize, Deserializer,
+};
+
+/// Buffered value of any self-describing format.
+/// Used to read enum tag before deciding how to update the enum.
+#[derive(Clone, Debug)]
+pub enum Content<'de> {
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    F32(f32),
+    F64(f64),
+    Char(char),
+    String(String),
+    Str(&'de str),
+    ByteBuf(Vec<u8>),
+    Bytes(&'de [u8]),
+    None,
+    Some(Box<Content<'de>>),
+    Unit,
+    Newtype(Box<Content<'de>>),
+    Seq(Vec<Content<'de>>),
+    Map(Vec<(Content<'de>, Content<'de>)>),
+}
+
+impl Content<'_> {
+    fn unexpected(&self) -> Unexpected<'_> {
+        match *self {
+            Content::Bool(value) => Unexpected::Bool(value),
+            Content::U8(value) => Unexpected::Unsigned(value.into()),
+            Content::U16(value) => Unexpected::Unsigned(value.into()),
+            Content::U32(value) => Unexpected::Unsigned(value.into()),
+            Content::U64(value) => Unexpected::Unsigned(value),
+            Content::U128(_) => Unexpected::Other("u128"),
+            Content::I8(value) => Unexpected::Signed(value.into()),
+            Content::I16(value) => Unexpected::Signed(value.into()),
+            Content::I32(value) => Unexpected::Signed(value.into()),
+            Content::I64(value) => Unexpected::Signed(value),
+            Content::I128(_) => Unexpected::Other("i128"),
+            Content::F32(value) => Unexpected::Float(value.into()),
+            Content::F64(value) => Unexpected::Float(value),
+            Content::Char(value) => Unexpected::Char(value),
+            Content::String(ref value) => Unexpected::Str(value),
+            Content::Str(value) => Unexpected::Str(value),
+            Content::ByteBuf(ref value) => Unexpected::Bytes(value),
+            Content::Bytes(value) => Unexpected::Bytes(value),
+            Content::None | Content::Some(_) => Unexpected::Option,
+            Content::Unit => Unexpected::Unit,
+            Content::Newtype(_) => Unexpected::NewtypeStruct,
+            Content::Seq(_) => Unexpected::Seq,
+            Content::Map(_) => Unexpected::Map,
+        }
+    }
+
+    fn is_key(&self, key: &str) -> bool {
+        self.as_key() == Some(key)
+    }
+
+    #[inline]
+    fn as_key(&self) -> Option<&str> {
+        match self {
+            Content::String(value) => Some(value),
+            Content::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Returns value of the entry with `key` if `content` is a map.
+pub fn find_entry<'a, 'de>(content: &'a Content<'de>, key: &str) -> Option<&'a Content<'de>> {
+    match content {
+        Content::Map(entries) => entries
+            .iter()
+            .find(|(k, _)| k.is_key(key))
+            .map(|(_, value)| value),
+        _ => None,
+    }
+}
+
+/// Removes entry with `key` if `content` is a map and returns its value.
+pub fn take_entry<'de>(content: &mut Content<'de>, key: &str) -> Option<Content<'de>> {
+    match content {
+        Content::Map(entries) => {
+            let idx = entries.iter().position(|(k, _)| k.is_key(key))?;
+            Some(entries.remove(idx).1)
+        }
+        _ => None,
+    }
+}
+
+impl<'de> Deserialize<'de> for Content<'de> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ContentVisitor)
+    }
+}
+
+struct ContentVisitor;
+
+macro_rules! visit_content {
+    ($($visit:ident($ty:ty) => $variant:ident),* $(,)?) => {
+        $(
+            #[inline]
+            fn $visit<E>(self, value: $ty) -> Result<Content<'de>, E>
+            where
+                E: Error,
+            {
+                Ok(Content::$variant(value.into()))
+            }
+        )*
+    };
+}
+
+impl<'de> Visitor<'de> for ContentVisitor {
+    type Value = Content<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    visit_content! {
+        visit_bool(bool) => Bool,
+        visit_u8(u8) => U8,
+        visit_u16(u16) => U16,
+        visit_u32(u32) => U32,
+        visit_u64(u64) => U64,
+        visit_u128(u128) => U128,
+        visit_i8(i8) => I8,
+        visit_i16(i16) => I16,
+        visit_i32(i32) => I32,
+        visit_i64(i64) => I64,
+        visit_i128(i128) => I128,
+        visit_f32(f32) => F32,
+        visit_f64(f64) => F64,
+        visit_char(char) => Char,
+        visit_str(&str) => String,
+        visit_borrowed_str(&'de str) => Str,
+        visit_string(String) => String,
+        visit_bytes(&[u8]) => ByteBuf,
+        visit_borrowed_bytes(&'de [u8]) => Bytes,
+        visit_byte_buf(Vec<u8>) => ByteBuf,
+    }
+
+    #[inline]
+    fn visit_none<E>(self) -> Result<Content<'de>, E>
+    where
+        E: Error,
+    {
+        Ok(Content::None)
+    }
+
+    #[inline]
+    fn visit_some<D>(self, deserializer: D) -> Result<Content<'de>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Content::deserialize(deserializer)?;
+        Ok(Content::Some(Box::new(value)))
+    }
+
+    #[inline]
+    fn visit_unit<E>(self) -> Result<Content<'de>, E>
+    where
+        E: Error,
+    {
+        Ok(Content::Unit)
+    }
+
+    #[inline]
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Content<'de>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Content::deserialize(deserializer)?;
+        Ok(Content::Newtype(Box::new(value)))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Content<'de>, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(element) = seq.next_element()? {
+            elements.push(element);
+        }
+        Ok(Content::Seq(elements))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Content<'de>, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(Content::Map(entries))
+    }
+}
+
+/// Deserializer that replays buffered [`Content`].
+pub struct ContentDeserializer<'de, E> {
+    content: Content<'de>,
+    marker: PhantomData<E>,
+}
+
+impl<'de, E> ContentDeserializer<'de, E> {
+    #[inline]
+    pub fn new(content: Content<'de>) -> Self {
+        ContentDeserializer {
+            content,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, E> IntoDeserializer<'de, E> for Content<'de>
+where
+    E: Error,
+{
+    type Deserializer = ContentDeserializer<'de, E>;
+
+    #[inline]
+    fn into_deserializer(self) -> ContentDeserializer<'de, E> {
+        ContentDeserializer::new(self)
+    }
+}
+
+impl<'de, E> Deserializer<'de> for ContentDeserializer<'de, E>
+where
+    E: Error,
+{
+    type Error = E;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content {
+            Content::Bool(value) => visitor.visit_bool(value),
+            Content::U8(value) => visitor.visit_u8(value),
+            Content::U16(value) => visitor.visit_u16(value),
+            Content::U32(value) => visitor.visit_u32(value),
+            Content::U64(value) => visitor.visit_u64(value),
+            Content::U128(value) => visitor.visit_u128(value),
+            Content::I8(value) => visitor.visit_i8(value),
+            Content::I16(value) => visitor.visit_i16(value),
+            Content::I32(value) => visitor.visit_i32(value),
+            Content::I64(value) => visitor.visit_i64(value),
+            Content::I128(value) => visitor.visit_i128(value),
+            Content::F32(value) => visitor.visit_f32(value),
+            Content::F64(value) => visitor.visit_f64(value),
+            Content::Char(value) => visitor.visit_char(value),
+            Content::String(value) => visitor.visit_string(value),
+            Content::Str(value) => visitor.visit_borrowed_str(value),
+            Content::ByteBuf(value) => visitor.visit_byte_buf(value),
+            Content::Bytes(value) => visitor.visit_borrowed_bytes(value),
+            Content::None => visitor.visit_none(),
+            Content::Some(value) => visitor.visit_some(ContentDeserializer::new(*value)),
+            Content::Unit => visitor.visit_unit(),
+            Content::Newtype(value) => {
+                visitor.visit_newtype_struct(ContentDeserializer::new(*value))
+            }
+            Content::Seq(elements) => {
+                SeqDeserializer::new(elements.into_iter()).deserialize_any(visitor)
+            }
+            Content::Map(entries) => {
+                MapDeserializer::new(entries.into_iter()).deserialize_any(visitor)
+            }
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content {
+            Content::None | Content::Unit => visitor.visit_none(),
+            Content::Some(value) => visitor.visit_some(ContentDeserializer::new(*value)),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content {
+            Content::Newtype(value) => {
+                visitor.visit_newtype_struct(ContentDeserializer::new(*value))
+            }
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content {
+            variant @ (Content::String(_) | Content::Str(_)) => {
+                visitor.visit_enum(ContentEnumAccess::new(variant, None))
+            }
+            Content::Map(entries) => {
+                let mut entries = entries.into_iter();
+                match (entries.next(), entries.next()) {
+                    (Some((variant, value)), None) => {
+                        visitor.visit_enum(ContentEnumAccess::new(variant, Some(value)))
+                    }
+                    _ => Err(E::invalid_value(Unexpected::Map, &"map with a single key")),
+                }
+            }
+            content => Err(E::invalid_type(content.unexpected(), &"string or map")),
+        }
+    }
+
+    #[inline]
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier
+    }
+}
+
+/// Enum access over buffered variant identifier and optional variant content.
+pub struct ContentEnumAccess<'de, E> {
+    variant: Content<'de>,
+    value: Option<Content<'de>>,
+    internal: bool,
+    marker: PhantomData<E>,
+}
+
+impl<'de, E> ContentEnumAccess<'de, E> {
+    #[inline]
+    pub fn new(variant: Content<'de>, value: Option<Content<'de>>) -> Self {
+        ContentEnumAccess {
+            variant,
+            value,
+            internal: false,
+            marker: PhantomData,
+        }
+    }
+
+    /// Access to internally tagged variant.
+    /// `rest` is the map without the tag entry, unit variant ignores it.
+    #[inline]
+    pub fn internal(variant: Content<'de>, rest: Content<'de>) -> Self {
+        ContentEnumAccess {
+            variant,
+            value: Some(rest),
+            internal: true,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, E> EnumAccess<'de> for ContentEnumAccess<'de, E>
+where
+    E: Error,
+{
+    type Error = E;
+    type Variant = ContentVariantAccess<'de, E>;
+
+    #[inline]
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), E>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(ContentDeserializer::new(self.variant))?;
+        let access = ContentVariantAccess {
+            value: self.value,
+            internal: self.internal,
+            marker: PhantomData,
+        };
+        Ok((variant, access))
+    }
+}
+
+pub struct ContentVariantAccess<'de, E> {
+    value: Option<Content<'de>>,
+    internal: bool,
+    marker: PhantomData<E>,
+}
+
+impl<'de, E> VariantAccess<'de> for ContentVariantAccess<'de, E>
+where
+    E: Error,
+{
+    type Error = E;
+
+    #[inline]
+    fn unit_variant(self) -> Result<(), E> {
+        match self.value {
+            None | Some(Content::Unit) => Ok(()),
+            // Internally tagged unit variant is a map with the tag, other entries are ignored.
+            Some(Content::Map(_)) if self.internal => Ok(()),
+            Some(content) => Err(E::invalid_type(content.unexpected(), &"unit variant")),
+        }
+    }
+
+    #[inline]
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, E>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.value {
+            Some(content) => seed.deserialize(ContentDeserializer::new(content)),
+            None => Err(E::invalid_type(Unexpected::UnitVariant, &"newtype variant")),
+        }
+    }
+
+    #[inline]
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Some(content) => ContentDeserializer::new(content).deserialize_any(visitor),
+            None => Err(E::invalid_type(Unexpected::UnitVariant, &"tuple variant")),
+        }
+    }
+
+    #[inline]
+    fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Some(content) => ContentDeserializer::new(content).deserialize_any(visitor),
+            None => Err(E::invalid_type(Unexpected::UnitVariant, &"struct variant")),
+        }
+    }
+}
+
+/// Entries of a map that are not fields of the struct.
+/// They are offered to flattened fields in order and the rest are reported as unknown fields.
+pub struct FlatEntries<'de> {
+    entries: Vec<Option<(Content<'de>, Content<'de>)>>,
+}
+
+impl<'de> FlatEntries<'de> {
+    #[inline]
+    pub fn new() -> Self {
+        FlatEntries {
+            entries: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn push(&mut self, key: Content<'de>, value: Content<'de>) {
+        self.entries.push(Some((key, value)));
+    }
+
+    /// Selects entries for flattened field.
+    /// With `filter` the field takes only entries with keys it accepts,
+    /// otherwise structs take entries with their field names and maps take all remaining entries.
+    #[inline]
+    pub fn select(&mut self, filter: Option<fn(&str) -> bool>) -> FlatSelection<'_, 'de> {
+        FlatSelection {
+            entries: &mut self.entries,
+            filter,
+        }
+    }
+
+    /// Fails if any entry was not consumed by flattened fields.
+    pub fn end<E>(self, fields: &'static [&'static str]) -> Result<(), E>
+    where
+        E: Error,
+    {
+        match self.entries.into_iter().flatten().next() {
+            None => Ok(()),
+            Some((Content::String(key), _)) => Err(E::unknown_field(&key, fields)),
+            Some((Content::Str(key), _)) => Err(E::unknown_field(key, fields)),
+            Some((key, _)) => Err(E::invalid_type(key.unexpected(), &"field identifier")),
+        }
+    }
+}
+
+impl Default for FlatEntries<'_> {
+    #[inline]
+    fn default() -> Self {
+        FlatEntries::new()
+    }
+}
+
+/// Entries of [`FlatEntries`] offered to single flattened field.
+pub struct FlatSelection<'a, 'de> {
+    entries: &'a mut Vec<Option<(Content<'de>, Content<'de>)>>,
+    filter: Option<fn(&str) -> bool>,
+}
+
+impl<'de> FlatSelection<'_, 'de> {
+    /// Deserializes flattened field with `seed` from the selected entries.
+    #[inline]
+    pub fn deserialize_seed<S, E>(self, seed: S) -> Result<S::Value, E>
+    where
+        S: DeserializeSeed<'de>,
+        E: Error,
+    {
+        seed.deserialize(FlatMapDeserializer {
+            entries: self.entries,
+            filter: self.filter,
+            marker: PhantomData,
+        })
+    }
+}
+
+/// Deserializer for flattened field.
+/// Types with filter take entries it accepts,
+/// other structs take entries with their field names and maps take all remaining entries.
+struct FlatMapDeserializer<'a, 'de, E> {
+    entries: &'a mut Vec<Option<(Content<'de>, Content<'de>)>>,
+    filter: Option<fn(&str) -> bool>,
+    marker: PhantomData<E>,
+}
+
+impl<'de, E> Deserializer<'de> for FlatMapDeserializer<'_, 'de, E>
+where
+    E: Error,
+{
+    type Error = E;
+
+    #[inline]
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    #[inline]
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(FlatMapAccess {
+            iter: self.entries.iter_mut(),
+            filter: self.filter,
+            fields: None,
+            value: None,
+            marker: PhantomData,
+        })
+    }
+
+    #[inline]
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(FlatMapAccess {
+            iter: self.entries.iter_mut(),
+            filter: self.filter,
+            fields: Some(fields),
+            value: None,
+            marker: PhantomData,
+        })
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct enum
+        identifier ignored_any
+    }
+}
+
+struct FlatMapAccess<'a, 'de, E> {
+    iter: core::slice::IterMut<'a, Option<(Content<'de>, Content<'de>)>>,
+    /// Accepts keys of the flattened type, takes precedence over `fields`.
+    filter: Option<fn(&str) -> bool>,
+    /// Field names of the struct, `None` for maps.
+    fields: Option<&'static [&'static str]>,
+    value: Option<Content<'de>>,
+    marker: PhantomData<E>,
+}
+
+impl<'de, E> MapAccess<'de> for FlatMapAccess<'_, 'de, E>
+where
+    E: Error,
+{
+    type Error = E;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, E>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        for entry in &mut self.iter {
+            let take = match (entry.as_ref(), self.filter, self.fields) {
+                (None, _, _) => false,
+                (Some((key, _)), Some(filter), _) => key.as_key().is_some_and(filter),
+                (Some(_), None, None) => true,
+                (Some((key, _)), None, Some(fields)) => {
+                    fields.iter().any(|field| key.is_key(field))
+                }
+            };
+
+            if take {
+                let (key, value) = entry.take().unwrap();
+                self.value = Some(value);
+                return seed.deserialize(ContentDeserializer::new(key)).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, E>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.value.take() {
+            Some(value) => seed.deserialize(ContentDeserializer::new(value)),
+            None => Err(E::custom("value is missing")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec};
+
+    use serde::de::value::Error;
+    use serde_derive::Deserialize;
+
+    use super::*;
+
+    fn buffer(json: &str) -> Content<'_> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Point {
+        x: u32,
+        y: Option<u32>,
+        tags: Vec<String>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Shape {
+        Empty,
+        Circle(u32),
+        Rect(u32, u32),
+        Poly { points: Vec<u32> },
+    }
+
+    #[test]
+    fn buffered_value_deserializes() {
+        let content = buffer(r#"{"x":1,"y":null,"tags":["a","b"]}"#);
+        let point = Point::deserialize(ContentDeserializer::<Error>::new(content)).unwrap();
+        assert_eq!(
+            point,
+            Point {
+                x: 1,
+                y: None,
+                tags: vec!["a".to_string(), "b".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn entries_are_found_and_taken() {
+        let mut content = buffer(r#"{"type":"A","x":1}"#);
+        assert!(matches!(
+            find_entry(&content, "type"),
+            Some(Content::Str("A"))
+        ));
+        assert!(find_entry(&content, "y").is_none());
+
+        assert!(matches!(
+            take_entry(&mut content, "type"),
+            Some(Content::Str("A"))
+        ));
+        assert!(find_entry(&content, "type").is_none());
+        assert!(take_entry(&mut buffer("[1]"), "type").is_none());
+    }
+
+    #[test]
+    fn enum_access_reads_variants() {
+        let shape = |variant: &'static str, value: Option<Content<'static>>| {
+            let access = ContentEnumAccess::<Error>::new(Content::Str(variant), value);
+            Shape::deserialize(EnumDeserializer(access))
+        };
+
+        assert_eq!(shape("Empty", None).unwrap(), Shape::Empty);
+        assert_eq!(
+            shape("Circle", Some(Content::U32(3))).unwrap(),
+            Shape::Circle(3)
+        );
+        assert_eq!(
+            shape("Rect", Some(buffer("[1,2]"))).unwrap(),
+            Shape::Rect(1, 2)
+        );
+        assert_eq!(
+            shape("Poly", Some(buffer(r#"{"points":[4]}"#))).unwrap(),
+            Shape::Poly { points: vec![4] }
+        );
+        assert!(shape("Circle", None).is_err());
+        assert!(shape("Empty", Some(buffer("{}"))).is_err());
+    }
+
+    #[test]
+    fn internal_unit_variant_ignores_rest() {
+        let access = ContentEnumAccess::<Error>::internal(Content::Str("Empty"), buffer("{}"));
+        assert_eq!(
+            Shape::deserialize(EnumDeserializer(access)).unwrap(),
+            Shape::Empty
+        );
+    }
+
+    /// Hands enum access to the enum visitor.
+    struct EnumDeserializer<'de>(ContentEnumAccess<'de, Error>);
+
+    impl<'de> Deserializer<'de> for EnumDeserializer<'de> {
+        type Error = Error;
+
+        fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+        where
+            V: Visitor<'de>,
+        {
+            visitor.visit_enum(self.0)
+        }
+
+        forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map struct enum identifier ignored_any
+        }
+    }
+}
@@ -1,8 +1,13 @@
-#[cfg(any(feature = "std", feature = "hashbrown"))]
+#[cfg(any(
+    feature = "std",
+    feature = "hashbrown",
+    feature = "indexmap",
+    feature = "heapless"
+))]
 use core::hash::{BuildHasher, Hash};
 
 #[cfg(feature = "alloc")]
-use alloc::{string::String, vec::Vec};
+use alloc::{borrow::Cow, boxed::Box, rc::Rc, string::String, vec::Vec};
 
 use serde::{
     de::{DeserializeOwned, DeserializeSeed},
@@ -12,41 +17,51 @@ use serde::{
 use crate::Figa;
 
 /// Uses default update behavior for the type.
-/// Not all types have default behavior, but usually type that supports
-/// at least one update behavior has default behavior.
+/// Default behavior is defined by the `Figa` implementation of the type.
 pub struct Default<'a, F>(pub &'a mut F);
 
+impl<'de, T> DeserializeSeed<'de> for Default<'_, T>
+where
+    T: Figa,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.0.update(deserializer)
+    }
+}
+
 macro_rules! default_replace {
         ($($types:ty $(where $($params:ident : $head_bound:ident $(+ $tail_bounds:ident)*),+)?)*) => {
             $(
-                impl<'de $($(, $params : $head_bound $(+ $tail_bounds)*)+)?> DeserializeSeed<'de> for Default<'_, $types> {
-                    type Value = ();
-
+                impl$(<$($params : $head_bound $(+ $tail_bounds)*),+>)? Figa for $types {
                     #[inline]
-                    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+                    fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
                     where
                         D: Deserializer<'de>,
                     {
-                        serde::Deserialize::deserialize_in_place(deserializer, self.0)
+                        serde::Deserialize::deserialize_in_place(deserializer, self)
                     }
                 }
             )*
         };
     }
 
-#[cfg(any(feature = "alloc", feature = "hashbrown"))]
+#[cfg(any(feature = "alloc", feature = "hashbrown", feature = "indexmap"))]
 macro_rules! default_append {
         ($($types:ty $(where $($params:ident : $head_bound:ident $(+ $tail_bounds:ident)*),+)?);* $(;)?) => {
             $(
-                impl<'de $($(, $params : $head_bound $(+ $tail_bounds)*)+)?> DeserializeSeed<'de> for Default<'_, $types> {
-                    type Value = ();
-
+                impl$(<$($params : $head_bound $(+ $tail_bounds)*),+>)? Figa for $types {
                     #[inline]
-                    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+                    fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
                     where
-                        D: serde::de::Deserializer<'de>,
+                        D: Deserializer<'de>,
                     {
-                        crate::append::Append(self.0).deserialize(deserializer)
+                        crate::append::Append(self).deserialize(deserializer)
                     }
                 }
             )*
@@ -56,15 +71,13 @@ macro_rules! default_append {
 macro_rules! default_update {
         ($($types:ty $(where $($params:ident : $head_bound:ident $(+ $tail_bounds:ident)*),+)?);* $(;)?) => {
             $(
-                impl<'de $($(, $params : $head_bound $(+ $tail_bounds)*)+)?> DeserializeSeed<'de> for Default<'_, $types> {
-                    type Value = ();
-
+                impl$(<$($params : $head_bound $(+ $tail_bounds)*),+>)? Figa for $types {
                     #[inline]
-                    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+                    fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
                     where
                         D: Deserializer<'de>,
                     {
-                        crate::update::Update(self.0).deserialize(deserializer)
+                        crate::update::UpdateElements(self).deserialize(deserializer)
                     }
                 }
             )*
@@ -79,6 +92,18 @@ default_replace!(String);
 #[cfg(feature = "alloc")]
 default_append!(Vec<T> where T: DeserializeOwned);
 
+#[cfg(feature = "alloc")]
+default_append! {
+    alloc::collections::VecDeque<T> where
+        T: DeserializeOwned;
+
+    alloc::collections::LinkedList<T> where
+        T: DeserializeOwned;
+
+    alloc::collections::BinaryHeap<T> where
+        T: DeserializeOwned + Ord;
+}
+
 #[cfg(feature = "alloc")]
 default_append! {
     alloc::collections::BTreeMap<K, V> where
@@ -113,7 +138,218 @@ default_append! {
         S: BuildHasher;
 }
 
+#[cfg(feature = "indexmap")]
+default_append! {
+    indexmap::IndexMap<K, V, S> where
+        K: DeserializeOwned + Eq + Hash,
+        V: DeserializeOwned,
+        S: BuildHasher;
+
+    indexmap::IndexSet<T, S> where
+        T: DeserializeOwned + Eq + Hash,
+        S: BuildHasher;
+}
+
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> Figa for heapless::Vec<T, N>
+where
+    T: DeserializeOwned,
+{
+    #[inline]
+    fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::append::Append(self).deserialize(deserializer)
+    }
+}
+
+/// Replaced as `String`.
+#[cfg(feature = "heapless")]
+impl<const N: usize> Figa for heapless::String<N> {
+    #[inline]
+    fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut value = heapless::String::new();
+        crate::append::Append(&mut value).deserialize(deserializer)?;
+        *self = value;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<K, V, S, const N: usize> Figa for heapless::IndexMap<K, V, S, N>
+where
+    K: DeserializeOwned + Eq + Hash,
+    V: DeserializeOwned,
+    S: BuildHasher,
+{
+    #[inline]
+    fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::append::Append(self).deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<K, V, const N: usize> Figa for heapless::LinearMap<K, V, N>
+where
+    K: DeserializeOwned + Eq,
+    V: DeserializeOwned,
+{
+    #[inline]
+    fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::append::Append(self).deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A> Figa for smallvec::SmallVec<A>
+where
+    A: smallvec::Array,
+    A::Item: DeserializeOwned,
+{
+    #[inline]
+    fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::append::Append(self).deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<T, const N: usize> Figa for arrayvec::ArrayVec<T, N>
+where
+    T: DeserializeOwned,
+{
+    #[inline]
+    fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::append::Append(self).deserialize(deserializer)
+    }
+}
+
 default_update! {
-    T where T: Figa;
     Option<T> where T: Figa + DeserializeOwned;
 }
+
+impl<T, const N: usize> Figa for [T; N]
+where
+    T: Figa + Clone,
+{
+    #[inline]
+    fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::update::UpdateElements(self).deserialize(deserializer)
+    }
+}
+
+macro_rules! default_update_tuple {
+    ($(($($name:ident)+))+) => {
+        $(
+            impl<$($name),+> Figa for ($($name,)+)
+            where
+                $($name: Figa + Clone,)+
+            {
+                #[inline]
+                fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    crate::update::UpdateElements(self).deserialize(deserializer)
+                }
+            }
+        )+
+    };
+}
+
+default_update_tuple! {
+    (T0)
+    (T0 T1)
+    (T0 T1 T2)
+    (T0 T1 T2 T3)
+    (T0 T1 T2 T3 T4)
+    (T0 T1 T2 T3 T4 T5)
+    (T0 T1 T2 T3 T4 T5 T6)
+    (T0 T1 T2 T3 T4 T5 T6 T7)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15)
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Figa for Box<T>
+where
+    T: Figa,
+{
+    #[inline]
+    fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        (**self).update(deserializer)
+    }
+}
+
+/// Shared value is cloned before update if it is not unique.
+#[cfg(feature = "alloc")]
+impl<T> Figa for Rc<T>
+where
+    T: Figa + Clone,
+{
+    #[inline]
+    fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Rc::make_mut(self).update(deserializer)
+    }
+}
+
+/// Shared value is cloned before update if it is not unique.
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<T> Figa for alloc::sync::Arc<T>
+where
+    T: Figa + Clone,
+{
+    #[inline]
+    fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        alloc::sync::Arc::make_mut(self).update(deserializer)
+    }
+}
+
+/// Borrowed value is cloned before update.
+#[cfg(feature = "alloc")]
+impl<T> Figa for Cow<'_, T>
+where
+    T: alloc::borrow::ToOwned + ?Sized,
+    T::Owned: Figa,
+{
+    #[inline]
+    fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.to_mut().update(deserializer)
+    }
+}
@@ -0,0 +1,43 @@
+use core::marker::PhantomData;
+
+/// Names of entries the type takes when it is flattened into enclosing map.
+/// Implemented by the derive macro for structs with named fields.
+pub trait FlatFields {
+    /// Returns `true` if `key` names a field of the type or of its flattened fields.
+    fn is_field(key: &str) -> bool;
+}
+
+/// Picks entries filter for a field with `#[figa(flatten)]`.
+///
+/// Method call `(&FlatProbe::<T>(PhantomData)).flat_fields()` resolves to [`FlatByFields`]
+/// if `T` implements [`FlatFields`] and to [`FlatByAny`] otherwise,
+/// so that flattened maps take all remaining entries.
+pub struct FlatProbe<T: ?Sized>(pub PhantomData<T>);
+
+pub trait FlatByFields {
+    fn flat_fields(&self) -> Option<fn(&str) -> bool>;
+}
+
+impl<T> FlatByFields for FlatProbe<T>
+where
+    T: FlatFields,
+{
+    #[inline]
+    fn flat_fields(&self) -> Option<fn(&str) -> bool> {
+        Some(T::is_field)
+    }
+}
+
+pub trait FlatByAny {
+    fn flat_fields(&self) -> Option<fn(&str) -> bool>;
+}
+
+impl<T> FlatByAny for &FlatProbe<T>
+where
+    T: ?Sized,
+{
+    #[inline]
+    fn flat_fields(&self) -> Option<fn(&str) -> bool> {
+        None
+    }
+}
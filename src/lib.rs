@@ -12,36 +12,138 @@ extern crate alloc;
 #[cfg(feature = "load")]
 pub mod load;
 
+#[cfg(feature = "std")]
+pub mod path;
+
+mod aggregate;
 mod append;
+#[cfg(feature = "alloc")]
+mod append_unique;
+mod bounded;
+#[cfg(feature = "alloc")]
+mod constraint;
+#[cfg(feature = "alloc")]
+mod content;
 mod default;
+mod flatten;
+mod locked;
 mod replace;
 mod update;
+#[cfg(feature = "alloc")]
+mod validate;
+mod variant;
 
 use serde::Deserializer;
 
 #[cfg(feature = "derive")]
 pub use figa_proc::Figa;
 
+#[cfg(feature = "std")]
+pub use path::RelativePath;
+
+#[cfg(feature = "alloc")]
+pub use constraint::{Constraint, ConstraintValue, Constraints, FieldConstraint};
+
+#[cfg(feature = "alloc")]
+pub use validate::{Validate, ValidationError};
+
 /// Trait for loadable layered configuration values.
 /// Uses `serde` for deserialization.
 /// Can be updated with other values of the same type to make a layered configuration.
 ///
-/// Can be derived for user-defined types.
+/// Can be derived for user-defined structs and enums.
+/// If the next layer names the enum variant that is already active, its fields are updated,
+/// otherwise the enum is replaced with freshly deserialized value using `serde::Deserialize`.
+/// Internally tagged, adjacently tagged and untagged enums require `alloc` feature.
+/// Layer without the tag updates the active variant.
+/// Untagged enum is replaced if the layer deserializes as a different variant.
+/// Untagged enum must implement `Clone`, the active variant is updated on a copy
+/// so a layer that fits no variant leaves the value unchanged.
 /// Derive macro accepts attributes to control how fields are loaded.
 /// - `#[figa(update)]` causes the field to be updated with the value from the next layer.
-///   This is default behavior when no attributes are specified.
 ///   Field type must implement `Figa` trait.
+///   `Option`, `Vec`, `VecDeque`, maps, arrays and tuples are updated element-wise instead,
+///   values with same keys or indices are updated using `Figa` trait and new values are added.
+///   `Box`, `Rc`, `Arc` and `Cow` apply `#[figa(update)]` to the value they point to.
 /// - `#[figa(replace)]` causes the field to be replaced with the value from the next layer. Field must implement `serde::Deserialize`.
 /// - `#[figa(append)]` causes the field to be appended with the value from the next layer.
 ///   Works on collections like `Vec` and `HashSet`, `HashMap`. Values with equal keys are replaced.
 ///   Value type must implement `serde::Deserialize`.
+///   For `String` fields separator can be specified with `#[figa(append, separator = ",")]`.
+///   It is inserted only when both existing and appended strings are not empty.
 /// - `#[figa(union)]` similar to the above but works for `HashSet`, `HashMap` and similar collections.
 ///   Values with equal keys are updated. Value type must implement `Figa` trait.
 ///   Value type must implement `serde::Deserialize` and `Figa`.
+/// - `#[figa(append_unique)]` similar to `append` but skips values that are already present.
+///   Works on `Vec` and preserves order of elements. Value type must implement `PartialEq`.
+///   `#[figa(append_unique = hash)]` finds present values by hash in linear time instead,
+///   value type must implement `Hash` and `Eq`. Requires `std` feature.
+/// - `#[figa(max)]`, `#[figa(min)]` and `#[figa(sum)]` combine the value from the next layer with the existing one.
+///   Work on numeric types. `sum` fails on overflow.
+/// - `#[figa(any)]` and `#[figa(all)]` combine `bool` value from the next layer with the existing one
+///   using logical OR and AND respectively.
+/// - `#[figa(max_len = N)]` limits length of `Vec` or byte length of `String` after each layer.
+///   Usually combined with `append`. What happens on overflow is controlled with `overflow = error | keep_first | keep_last`.
+///   `error` fails to load the layer and is the default, `keep_first` drops excess values from the end
+///   and `keep_last` drops them from the beginning, making a sliding window.
+/// - `#[figa(path)]` causes `PathBuf` or `Option<PathBuf>` field to be replaced with the path from the next layer
+///   resolved relative to the layer's origin file. See [`path::with_origin`].
+///   Alternatively use [`RelativePath`] as field type.
+/// - `#[figa(locked)]` prevents later layers from changing the field.
+///   Layer that mentions locked field fails to load.
+///   With `#[figa(locked = ignore)]` the value is ignored instead, and a warning is logged if `log` feature is enabled.
+///   The field is established by the first layer, or by the first layer that sets `Option` field to `Some`,
+///   in which case the field's own strategy is used.
+/// - `#[figa(with = "path")]` causes the field to be updated by a user-supplied strategy.
+///   `path` is called with `&mut` reference to the field and must return `serde::de::DeserializeSeed` with `Value = ()`.
+///   It can be a function or a tuple struct constructor, similar to the built-in strategies.
+/// - `#[figa(bound = "T: Trait")]` replaces bounds on type parameters inferred for the field.
+///   On the type itself it replaces all inferred bounds.
+/// - `#[figa(flatten)]` updates named field from keys of the enclosing map that are not fields of the type,
+///   matching `#[serde(flatten)]`. Several fields may be flattened; each consumes the keys it recognizes
+///   and keys left unrecognized are reported as unknown fields. Requires `alloc` feature.
+/// - `#[figa(skip)]` excludes the field from updates, matching `#[serde(skip)]`.
+///   Layers that mention the field fail with unknown field error. Skipped fields need no `Deserialize` implementation.
+/// - `#[figa(validate = "path")]` checks the field with a function that accepts reference to the field
+///   and returns `Result<(), E>` where `E: Display`. `#[figa(validate)]` checks the field with its [`Validate`] implementation.
+///   On the type itself `#[figa(validate = "path")]` is called with reference to the whole value
+///   after fields are checked, which suits cross-field rules.
+///   Types with these attributes implement [`Validate`]. Errors carry path to the field. Requires `alloc` feature.
+/// - `#[figa(validate_on = update)]` on the type validates it after every layer, so the layer with invalid value fails to load.
+///   With `#[figa(validate_on = load)]`, the default, [`Validate::validate`] is called once all layers are loaded.
+/// - `#[figa(range = 1..=65535)]`, `#[figa(len = 1..)]`, `#[figa(pattern = "^[a-z]+$")]` and `#[figa(one_of = ["a", "b"])]`
+///   constrain the field value. Constraints are checked on updated copy of the field before it is assigned,
+///   so the layer that introduced invalid value fails to load and the field keeps its value.
+///   Constrained fields must implement `Clone`. [`Validate`] implementation checks them too. `len` measures strings in bytes.
+///   On `Option` fields constraints check the contained value and `None` always passes.
+///   `range` and `len` take range expressions. Types with constraints implement [`Constraints`],
+///   which lists them with evaluated bounds and values for schema generation.
+///   `pattern` requires `regex` feature and is checked at compile time, other constraints require `alloc` feature.
+/// - `#[figa(default)]` on a struct generates `Default` implementation, which can serve as the first layer.
+///   Fields take the value of `#[figa(default = expr)]` or their own `Default` value.
+///   Generated value counts as the first layer, so `locked` fields keep their default value,
+///   e.g. `#[figa(default = true, locked)]`. `Option` field that defaults to `None` can still be set by the first layer that mentions it.
+///
+/// Generic types are supported. Bounds are inferred for fields which types mention type parameters,
+/// based on their update strategy.
+/// `serde` attributes `rename`, `rename_all`, `rename_all_fields` and `alias` are honoured,
+/// so update layers use the same keys as `serde::Deserialize`.
+/// `#[figa(crate = "path")]` on the type sets path to `figa` crate for generated code,
+/// for crates that use the derive through a re-export.
+///
+/// Fields without attributes are updated using their `Figa` implementation.
+/// `Figa` is implemented for primitives and `String` that are replaced,
+/// for `Option` that is updated if it contains a value and replaced otherwise,
+/// and for standard collections that are appended.
+/// Tuples up to 16 elements and arrays are updated positionally, shorter sequence updates only a prefix.
+/// Their elements must implement `Clone`, the layer is applied to a copy so a failed layer leaves the value unchanged.
+/// `Box`, `Rc`, `Arc` and `Cow` pass updates through to the value they point to.
+/// Shared or borrowed values are cloned before update, so other owners are not affected.
 ///
 /// This trait only defines `load` method to load next layer to the existing configuration value.
 /// First should be loaded by other means.
 /// Some functions load the first layer using `serde::Deserialize` trait.
+/// Alternatively derive `Default` with `#[figa(default)]` and start from `T::default()`.
 pub trait Figa {
     /// Update next layer from a deserializer.
     fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
@@ -57,17 +159,58 @@ pub mod private {
     pub use str;
 
     pub use core::{
+        clone::Clone,
+        default::Default as DefaultValue,
         fmt::{Formatter, Result as FmtResult},
         hash::Hash,
+        marker::PhantomData,
+        ops::Bound,
+        option::Option::{None, Some},
         result::Result::{self, Err, Ok},
+        unreachable,
     };
 
     pub use serde::de::{
-        Deserialize, DeserializeOwned, DeserializeSeed, Deserializer, Error as DeError, MapAccess,
-        SeqAccess, Visitor,
+        Deserialize, DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, Error as DeError,
+        MapAccess, SeqAccess, VariantAccess, Visitor,
+    };
+
+    pub use crate::{
+        aggregate::{All, Any, Max, Min, Sum},
+        append::Append,
+        bounded::{bound, Bounded, Overflow},
+        default::Default,
+        flatten::{FlatByAny, FlatByFields, FlatFields, FlatProbe},
+        locked::{LockPolicy, Locked},
+        replace::Replace,
+        update::{Update, UpdateByElements, UpdateByFiga, UpdateElements, UpdateProbe},
+        variant::VariantDeserializer,
+        Figa,
     };
 
-    pub use crate::{append::Append, default::Default, replace::Replace, update::Update, Figa};
+    #[cfg(feature = "alloc")]
+    pub use alloc::vec::Vec;
+
+    #[cfg(feature = "alloc")]
+    pub use crate::{
+        append::AppendSeparated,
+        append_unique::AppendUnique,
+        constraint::{
+            check_len, check_one_of, check_range, constrained, field_constraint, one_of_values,
+            ValueByConversion, ValueByDebug, ValueProbe,
+        },
+        content::{
+            find_entry, take_entry, Content, ContentDeserializer, ContentEnumAccess, FlatEntries,
+        },
+        validate::{validate_nested, validate_with, validated},
+        Validate,
+    };
+
+    #[cfg(feature = "std")]
+    pub use crate::{append_unique::AppendUniqueHash, path::ResolvePath};
+
+    #[cfg(feature = "regex")]
+    pub use crate::constraint::{check_pattern, Pattern};
 
     pub struct UnitStructVisitor;
 
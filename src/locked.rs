@@ -0,0 +1,47 @@
+use serde::{
+    de::{DeserializeSeed, Error, IgnoredAny},
+    Deserialize, Deserializer,
+};
+
+/// Policy for layers that mention locked field.
+pub enum LockPolicy {
+    /// Fail to load the layer.
+    Error,
+
+    /// Ignore the value from the layer.
+    /// Emits a warning if `log` feature is enabled.
+    Ignore,
+}
+
+/// Rejects or ignores new value for locked field.
+pub struct Locked {
+    pub field: &'static str,
+    pub policy: LockPolicy,
+}
+
+impl<'de> DeserializeSeed<'de> for Locked {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match self.policy {
+            LockPolicy::Error => Err(D::Error::custom(format_args!(
+                "field `{}` is locked and cannot be changed by later layers",
+                self.field
+            ))),
+            LockPolicy::Ignore => {
+                IgnoredAny::deserialize(deserializer)?;
+
+                #[cfg(feature = "log")]
+                log::warn!(
+                    "field `{}` is locked, value from later layer is ignored",
+                    self.field
+                );
+
+                Ok(())
+            }
+        }
+    }
+}
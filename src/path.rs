@@ -0,0 +1,167 @@
+//! Paths resolved relative to the layer they came from.
+//!
+//! Loader that knows origin file of a layer should update configuration within [`with_origin`].
+//! Relative paths loaded as [`RelativePath`] or with `#[figa(path)]` attribute
+//! are then resolved against directory of that file instead of current working directory.
+
+use std::{
+    cell::RefCell,
+    fmt,
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeSeed, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Figa;
+
+std::thread_local! {
+    static ORIGIN: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+}
+
+/// Calls `f` with `file` set as origin of the layer.
+/// Relative paths deserialized within `f` are resolved against directory of the `file`.
+///
+/// Calls can be nested. Previous origin is restored when `f` returns.
+pub fn with_origin<R>(file: &Path, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<PathBuf>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            ORIGIN.with(|origin| *origin.borrow_mut() = self.0.take());
+        }
+    }
+
+    let dir = file.parent().map(Path::to_path_buf);
+    let _restore = Restore(ORIGIN.with(|origin| origin.replace(dir)));
+    f()
+}
+
+/// Resolves path against directory of the current layer origin.
+/// Absolute paths and paths loaded without origin are returned as is.
+pub fn resolve(path: PathBuf) -> PathBuf {
+    if path.is_absolute() {
+        return path;
+    }
+
+    ORIGIN.with(|origin| match &*origin.borrow() {
+        None => path,
+        Some(dir) => dir.join(path),
+    })
+}
+
+/// Path that is resolved relative to the layer that set it.
+///
+/// Deserializes from a path and resolves it with [`resolve`].
+/// Updating replaces the path with the resolved one from the next layer.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RelativePath {
+    path: PathBuf,
+}
+
+impl RelativePath {
+    /// Returns resolved path.
+    #[inline]
+    pub fn as_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns resolved path.
+    #[inline]
+    pub fn into_path_buf(self) -> PathBuf {
+        self.path
+    }
+}
+
+impl fmt::Debug for RelativePath {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.path, f)
+    }
+}
+
+impl Deref for RelativePath {
+    type Target = Path;
+
+    #[inline]
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for RelativePath {
+    #[inline]
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl From<RelativePath> for PathBuf {
+    #[inline]
+    fn from(path: RelativePath) -> PathBuf {
+        path.path
+    }
+}
+
+impl Serialize for RelativePath {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.path.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RelativePath {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let path = PathBuf::deserialize(deserializer)?;
+        Ok(RelativePath {
+            path: resolve(path),
+        })
+    }
+}
+
+impl Figa for RelativePath {
+    #[inline]
+    fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        *self = RelativePath::deserialize(deserializer)?;
+        Ok(())
+    }
+}
+
+/// Replaces path with the one from the next layer resolved with [`resolve`].
+pub struct ResolvePath<'a, F>(pub &'a mut F);
+
+impl<'de> DeserializeSeed<'de> for ResolvePath<'_, PathBuf> {
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        *self.0 = resolve(PathBuf::deserialize(deserializer)?);
+        Ok(())
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for ResolvePath<'_, Option<PathBuf>> {
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        *self.0 = Option::<PathBuf>::deserialize(deserializer)?.map(resolve);
+        Ok(())
+    }
+}
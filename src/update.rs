@@ -1,18 +1,24 @@
-use core::fmt;
+use core::{fmt, marker::PhantomData};
 
-#[cfg(any(feature = "std", feature = "hashbrown"))]
+#[cfg(any(
+    feature = "std",
+    feature = "hashbrown",
+    feature = "indexmap",
+    feature = "heapless"
+))]
 use core::hash::{BuildHasher, Hash};
 
 #[cfg(feature = "alloc")]
-use alloc::vec::Vec;
+use alloc::{borrow::Cow, collections::VecDeque, rc::Rc, vec::Vec};
 
 use serde::{
-    de::{DeserializeOwned, DeserializeSeed, Visitor},
+    de::{DeserializeOwned, DeserializeSeed, Error, IgnoredAny, SeqAccess, Visitor},
     Deserializer,
 };
 
 use crate::Figa;
 
+/// Updates the value using its `Figa` implementation.
 pub struct Update<'a, T>(pub &'a mut T);
 
 impl<'de, T> DeserializeSeed<'de> for Update<'_, T>
@@ -30,7 +36,49 @@ where
     }
 }
 
-impl<'de, T> DeserializeSeed<'de> for Update<'_, Option<T>>
+/// Updates elements of `Option`, sequences and maps using their `Figa` implementation.
+/// New elements are added.
+/// Derive macro uses it instead of [`Update`] for fields of these types with `#[figa(update)]`.
+pub struct UpdateElements<'a, T>(pub &'a mut T);
+
+/// Picks seed for a field with `#[figa(update)]`.
+/// Types with [`UpdateElements`] implementation are updated element-wise,
+/// other types are updated with their `Figa` implementation.
+///
+/// Method call `(&UpdateProbe::<T>(PhantomData)).update_seed(value)` resolves to [`UpdateByElements`]
+/// implemented for the probe itself if it applies, and to [`UpdateByFiga`]
+/// implemented for the reference to the probe otherwise.
+pub struct UpdateProbe<T: ?Sized>(pub PhantomData<T>);
+
+pub trait UpdateByElements<T> {
+    fn update_seed<'a>(&self, value: &'a mut T) -> UpdateElements<'a, T>;
+}
+
+impl<T> UpdateByElements<T> for UpdateProbe<T>
+where
+    for<'a, 'de> UpdateElements<'a, T>: DeserializeSeed<'de, Value = ()>,
+{
+    #[inline]
+    fn update_seed<'a>(&self, value: &'a mut T) -> UpdateElements<'a, T> {
+        UpdateElements(value)
+    }
+}
+
+pub trait UpdateByFiga<T> {
+    fn update_seed<'a>(&self, value: &'a mut T) -> Update<'a, T>;
+}
+
+impl<T> UpdateByFiga<T> for &UpdateProbe<T>
+where
+    T: Figa,
+{
+    #[inline]
+    fn update_seed<'a>(&self, value: &'a mut T) -> Update<'a, T> {
+        Update(value)
+    }
+}
+
+impl<'de, T> DeserializeSeed<'de> for UpdateElements<'_, Option<T>>
 where
     T: DeserializeOwned + Figa,
 {
@@ -78,8 +126,78 @@ where
     }
 }
 
+/// Shared value is cloned before update if it is not unique.
 #[cfg(feature = "alloc")]
-impl<'de, T> DeserializeSeed<'de> for Update<'_, Vec<T>>
+impl<'de, T> DeserializeSeed<'de> for UpdateElements<'_, Rc<T>>
+where
+    T: Clone,
+    for<'a> UpdateElements<'a, T>: DeserializeSeed<'de, Value = ()>,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        UpdateElements(Rc::make_mut(self.0)).deserialize(deserializer)
+    }
+}
+
+/// Shared value is cloned before update if it is not unique.
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<'de, T> DeserializeSeed<'de> for UpdateElements<'_, alloc::sync::Arc<T>>
+where
+    T: Clone,
+    for<'a> UpdateElements<'a, T>: DeserializeSeed<'de, Value = ()>,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        UpdateElements(alloc::sync::Arc::make_mut(self.0)).deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, T> DeserializeSeed<'de> for UpdateElements<'_, alloc::boxed::Box<T>>
+where
+    for<'a> UpdateElements<'a, T>: DeserializeSeed<'de, Value = ()>,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        UpdateElements(&mut **self.0).deserialize(deserializer)
+    }
+}
+
+/// Borrowed value is cloned before update.
+#[cfg(feature = "alloc")]
+impl<'de, T> DeserializeSeed<'de> for UpdateElements<'_, Cow<'_, T>>
+where
+    T: alloc::borrow::ToOwned + ?Sized,
+    for<'a> UpdateElements<'a, T::Owned>: DeserializeSeed<'de, Value = ()>,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        UpdateElements(self.0.to_mut()).deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, T> DeserializeSeed<'de> for UpdateElements<'_, Vec<T>>
 where
     T: DeserializeOwned + Figa,
 {
@@ -95,7 +213,7 @@ where
 }
 
 #[cfg(feature = "alloc")]
-impl<'de, T> Visitor<'de> for Update<'_, Vec<T>>
+impl<'de, T> Visitor<'de> for UpdateElements<'_, Vec<T>>
 where
     T: DeserializeOwned + Figa,
 {
@@ -118,7 +236,10 @@ where
                 };
                 self.0.push(value);
             } else {
-                if seq.next_element_seed(Update(&mut self.0[idx]))?.is_none() {
+                if seq
+                    .next_element_seed(crate::default::Default(&mut self.0[idx]))?
+                    .is_none()
+                {
                     break;
                 }
             }
@@ -128,10 +249,190 @@ where
     }
 }
 
-#[cfg(any(feature = "alloc", feature = "hashbrown"))]
+#[cfg(feature = "alloc")]
+impl<'de, T> DeserializeSeed<'de> for UpdateElements<'_, VecDeque<T>>
+where
+    T: DeserializeOwned + Figa,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, T> Visitor<'de> for UpdateElements<'_, VecDeque<T>>
+where
+    T: DeserializeOwned + Figa,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("sequence")
+    }
+
+    #[inline]
+    fn visit_seq<A>(self, mut seq: A) -> Result<(), A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut idx = 0;
+        loop {
+            if idx >= self.0.len() {
+                let Some(value) = seq.next_element()? else {
+                    break;
+                };
+                self.0.push_back(value);
+            } else {
+                if seq
+                    .next_element_seed(crate::default::Default(&mut self.0[idx]))?
+                    .is_none()
+                {
+                    break;
+                }
+            }
+            idx += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Elements are updated positionally.
+/// Shorter sequence updates only a prefix of the array.
+/// Elements are updated on a copy, so the array is unchanged if the layer fails.
+impl<'de, T, const N: usize> DeserializeSeed<'de> for UpdateElements<'_, [T; N]>
+where
+    T: Figa + Clone,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(N, self)
+    }
+}
+
+impl<'de, T, const N: usize> Visitor<'de> for UpdateElements<'_, [T; N]>
+where
+    T: Figa + Clone,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "array of at most {} elements", N)
+    }
+
+    #[inline]
+    fn visit_seq<A>(self, mut seq: A) -> Result<(), A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut updated = self.0.clone();
+        for value in updated.iter_mut() {
+            if seq
+                .next_element_seed(crate::default::Default(value))?
+                .is_none()
+            {
+                *self.0 = updated;
+                return Ok(());
+            }
+        }
+        if seq.next_element::<IgnoredAny>()?.is_some() {
+            return Err(A::Error::invalid_length(N + 1, &self));
+        }
+        *self.0 = updated;
+        Ok(())
+    }
+}
+
+macro_rules! update_tuple {
+    ($($len:literal => ($($name:ident $idx:tt)+))+) => {
+        $(
+            /// Elements are updated positionally.
+            /// Shorter sequence updates only a prefix of the tuple.
+            /// Elements are updated on a copy, so the tuple is unchanged if the layer fails.
+            impl<'de, $($name),+> DeserializeSeed<'de> for UpdateElements<'_, ($($name,)+)>
+            where
+                $($name: Figa + Clone,)+
+            {
+                type Value = ();
+
+                #[inline]
+                fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    deserializer.deserialize_tuple($len, self)
+                }
+            }
+
+            impl<'de, $($name),+> Visitor<'de> for UpdateElements<'_, ($($name,)+)>
+            where
+                $($name: Figa + Clone,)+
+            {
+                type Value = ();
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str(concat!("tuple of at most ", $len, " elements"))
+                }
+
+                #[inline]
+                fn visit_seq<A>(self, mut seq: A) -> Result<(), A::Error>
+                where
+                    A: SeqAccess<'de>,
+                {
+                    let mut updated = self.0.clone();
+                    $(
+                        if seq
+                            .next_element_seed(crate::default::Default(&mut updated.$idx))?
+                            .is_none()
+                        {
+                            *self.0 = updated;
+                            return Ok(());
+                        }
+                    )+
+                    if seq.next_element::<IgnoredAny>()?.is_some() {
+                        return Err(A::Error::invalid_length($len + 1, &self));
+                    }
+                    *self.0 = updated;
+                    Ok(())
+                }
+            }
+        )+
+    };
+}
+
+update_tuple! {
+    1 => (T0 0)
+    2 => (T0 0 T1 1)
+    3 => (T0 0 T1 1 T2 2)
+    4 => (T0 0 T1 1 T2 2 T3 3)
+    5 => (T0 0 T1 1 T2 2 T3 3 T4 4)
+    6 => (T0 0 T1 1 T2 2 T3 3 T4 4 T5 5)
+    7 => (T0 0 T1 1 T2 2 T3 3 T4 4 T5 5 T6 6)
+    8 => (T0 0 T1 1 T2 2 T3 3 T4 4 T5 5 T6 6 T7 7)
+    9 => (T0 0 T1 1 T2 2 T3 3 T4 4 T5 5 T6 6 T7 7 T8 8)
+    10 => (T0 0 T1 1 T2 2 T3 3 T4 4 T5 5 T6 6 T7 7 T8 8 T9 9)
+    11 => (T0 0 T1 1 T2 2 T3 3 T4 4 T5 5 T6 6 T7 7 T8 8 T9 9 T10 10)
+    12 => (T0 0 T1 1 T2 2 T3 3 T4 4 T5 5 T6 6 T7 7 T8 8 T9 9 T10 10 T11 11)
+    13 => (T0 0 T1 1 T2 2 T3 3 T4 4 T5 5 T6 6 T7 7 T8 8 T9 9 T10 10 T11 11 T12 12)
+    14 => (T0 0 T1 1 T2 2 T3 3 T4 4 T5 5 T6 6 T7 7 T8 8 T9 9 T10 10 T11 11 T12 12 T13 13)
+    15 => (T0 0 T1 1 T2 2 T3 3 T4 4 T5 5 T6 6 T7 7 T8 8 T9 9 T10 10 T11 11 T12 12 T13 13 T14 14)
+    16 => (T0 0 T1 1 T2 2 T3 3 T4 4 T5 5 T6 6 T7 7 T8 8 T9 9 T10 10 T11 11 T12 12 T13 13 T14 14 T15 15)
+}
+
+#[cfg(any(feature = "alloc", feature = "hashbrown", feature = "indexmap"))]
 macro_rules! update_map {
     ($($q:ident::)*{Entry, $map:ident<K, V $(, $tail:ident)*>} $(where $($param:ident : $bound:path),* $(,)?)?) => {
-        impl<'a, 'de, K, V $(, $tail)*> DeserializeSeed<'de> for Update<'a, $($q::)* $map<K, V $(, $tail)*>>
+        impl<'a, 'de, K, V $(, $tail)*> DeserializeSeed<'de> for UpdateElements<'a, $($q::)* $map<K, V $(, $tail)*>>
         where
             K: DeserializeOwned,
             V: DeserializeOwned + Figa,
@@ -149,7 +450,7 @@ macro_rules! update_map {
         }
 
 
-        impl<'a, 'de, K, V $(, $tail)*> Visitor<'de> for Update<'a, $($q::)* $map<K, V $(, $tail)*>>
+        impl<'a, 'de, K, V $(, $tail)*> Visitor<'de> for UpdateElements<'a, $($q::)* $map<K, V $(, $tail)*>>
         where
             K: DeserializeOwned,
             V: DeserializeOwned + Figa,
@@ -169,7 +470,7 @@ macro_rules! update_map {
                 while let Some(key) = map.next_key()? {
                     match self.0.entry(key) {
                         $($q::)* Entry::Occupied(mut entry) => {
-                            map.next_value_seed(Update(entry.get_mut()))?;
+                            map.next_value_seed(crate::default::Default(entry.get_mut()))?;
                         }
                         $($q::)* Entry::Vacant(entry) => {
                             entry.insert(map.next_value()?);
@@ -190,3 +491,290 @@ update_map!(std::collections::hash_map::{Entry, HashMap<K, V, S>} where K: Eq, K
 
 #[cfg(feature = "hashbrown")]
 update_map!(hashbrown::hash_map::{Entry, HashMap<K, V, S>} where K: Eq, K: Hash, S: BuildHasher);
+
+#[cfg(feature = "indexmap")]
+update_map!(indexmap::map::{Entry, IndexMap<K, V, S>} where K: Eq, K: Hash, S: BuildHasher);
+
+#[cfg(feature = "heapless")]
+impl<'de, T, const N: usize> DeserializeSeed<'de> for UpdateElements<'_, heapless::Vec<T, N>>
+where
+    T: DeserializeOwned + Figa,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'de, T, const N: usize> Visitor<'de> for UpdateElements<'_, heapless::Vec<T, N>>
+where
+    T: DeserializeOwned + Figa,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "sequence of at most {} elements", N)
+    }
+
+    #[inline]
+    fn visit_seq<A>(self, mut seq: A) -> Result<(), A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let len = self.0.len();
+        let mut idx = 0;
+        loop {
+            if idx >= self.0.len() {
+                let value = match seq.next_element() {
+                    Ok(Some(value)) => value,
+                    Ok(None) => break,
+                    Err(err) => {
+                        self.0.truncate(len);
+                        return Err(err);
+                    }
+                };
+                if self.0.push(value).is_err() {
+                    self.0.truncate(len);
+                    return Err(A::Error::invalid_length(N + 1, &self));
+                }
+            } else {
+                if seq
+                    .next_element_seed(crate::default::Default(&mut self.0[idx]))?
+                    .is_none()
+                {
+                    break;
+                }
+            }
+            idx += 1;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'de, K, V, S, const N: usize> DeserializeSeed<'de>
+    for UpdateElements<'_, heapless::IndexMap<K, V, S, N>>
+where
+    K: DeserializeOwned + Eq + Hash,
+    V: DeserializeOwned + Figa,
+    S: BuildHasher,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'de, K, V, S, const N: usize> Visitor<'de>
+    for UpdateElements<'_, heapless::IndexMap<K, V, S, N>>
+where
+    K: DeserializeOwned + Eq + Hash,
+    V: DeserializeOwned + Figa,
+    S: BuildHasher,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "map of at most {} entries", N)
+    }
+
+    #[inline]
+    fn visit_map<M>(self, mut map: M) -> Result<(), M::Error>
+    where
+        M: serde::de::MapAccess<'de>,
+    {
+        while let Some(key) = map.next_key()? {
+            match self.0.entry(key) {
+                heapless::Entry::Occupied(mut entry) => {
+                    map.next_value_seed(crate::default::Default(entry.get_mut()))?;
+                }
+                heapless::Entry::Vacant(entry) => {
+                    if entry.insert(map.next_value()?).is_err() {
+                        return Err(M::Error::invalid_length(N + 1, &self));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'de, K, V, const N: usize> DeserializeSeed<'de>
+    for UpdateElements<'_, heapless::LinearMap<K, V, N>>
+where
+    K: DeserializeOwned + Eq,
+    V: DeserializeOwned + Figa,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'de, K, V, const N: usize> Visitor<'de> for UpdateElements<'_, heapless::LinearMap<K, V, N>>
+where
+    K: DeserializeOwned + Eq,
+    V: DeserializeOwned + Figa,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "map of at most {} entries", N)
+    }
+
+    #[inline]
+    fn visit_map<M>(self, mut map: M) -> Result<(), M::Error>
+    where
+        M: serde::de::MapAccess<'de>,
+    {
+        while let Some(key) = map.next_key::<K>()? {
+            match self.0.get_mut(&key) {
+                Some(value) => {
+                    map.next_value_seed(crate::default::Default(value))?;
+                }
+                None => {
+                    if self.0.insert(key, map.next_value()?).is_err() {
+                        return Err(M::Error::invalid_length(N + 1, &self));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<'de, A> DeserializeSeed<'de> for UpdateElements<'_, smallvec::SmallVec<A>>
+where
+    A: smallvec::Array,
+    A::Item: DeserializeOwned + Figa,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<'de, A> Visitor<'de> for UpdateElements<'_, smallvec::SmallVec<A>>
+where
+    A: smallvec::Array,
+    A::Item: DeserializeOwned + Figa,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("sequence")
+    }
+
+    #[inline]
+    fn visit_seq<X>(self, mut seq: X) -> Result<(), X::Error>
+    where
+        X: SeqAccess<'de>,
+    {
+        let mut idx = 0;
+        loop {
+            if idx >= self.0.len() {
+                let Some(value) = seq.next_element()? else {
+                    break;
+                };
+                self.0.push(value);
+            } else {
+                if seq
+                    .next_element_seed(crate::default::Default(&mut self.0[idx]))?
+                    .is_none()
+                {
+                    break;
+                }
+            }
+            idx += 1;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<'de, T, const N: usize> DeserializeSeed<'de> for UpdateElements<'_, arrayvec::ArrayVec<T, N>>
+where
+    T: DeserializeOwned + Figa,
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<'de, T, const N: usize> Visitor<'de> for UpdateElements<'_, arrayvec::ArrayVec<T, N>>
+where
+    T: DeserializeOwned + Figa,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "sequence of at most {} elements", N)
+    }
+
+    #[inline]
+    fn visit_seq<X>(self, mut seq: X) -> Result<(), X::Error>
+    where
+        X: SeqAccess<'de>,
+    {
+        let len = self.0.len();
+        let mut idx = 0;
+        loop {
+            if idx >= self.0.len() {
+                let value = match seq.next_element() {
+                    Ok(Some(value)) => value,
+                    Ok(None) => break,
+                    Err(err) => {
+                        self.0.truncate(len);
+                        return Err(err);
+                    }
+                };
+                if self.0.try_push(value).is_err() {
+                    self.0.truncate(len);
+                    return Err(X::Error::invalid_length(N + 1, &self));
+                }
+            } else {
+                if seq
+                    .next_element_seed(crate::default::Default(&mut self.0[idx]))?
+                    .is_none()
+                {
+                    break;
+                }
+            }
+            idx += 1;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use serde::de::Error;
+
+/// Error reported by a validator.
+/// Carries path to the field that failed validation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    path: String,
+    message: String,
+}
+
+impl ValidationError {
+    /// Returns new error with empty path.
+    pub fn new(message: impl fmt::Display) -> Self {
+        ValidationError {
+            path: String::new(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Dot-separated path to the field that failed validation.
+    /// Empty if the value itself failed validation.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Message reported by the validator.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Prepends field name to the path.
+    /// Used when error is propagated from a nested value.
+    pub fn in_field(mut self, field: &str) -> Self {
+        if field.is_empty() {
+            return self;
+        }
+        if self.path.is_empty() {
+            self.path = field.to_string();
+        } else {
+            self.path = alloc::format!("{}.{}", field, self.path);
+        }
+        self
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "field `{}` is invalid: {}", self.path, self.message)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValidationError {}
+
+/// Trait for configuration values that can be checked after loading.
+///
+/// Derive macro implements this trait along with [`Figa`](crate::Figa)
+/// for types with `#[figa(validate)]` attributes.
+/// With `#[figa(validate_on = update)]` the value is validated after every layer,
+/// so the error is reported for the layer that introduced invalid value.
+/// Otherwise `validate` should be called once all layers are loaded.
+pub trait Validate {
+    /// Checks the value and returns first error found.
+    fn validate(&self) -> Result<(), ValidationError>;
+}
+
+impl<T> Validate for Option<T>
+where
+    T: Validate,
+{
+    fn validate(&self) -> Result<(), ValidationError> {
+        match self {
+            None => Ok(()),
+            Some(value) => value.validate(),
+        }
+    }
+}
+
+impl<T> Validate for Box<T>
+where
+    T: Validate + ?Sized,
+{
+    fn validate(&self) -> Result<(), ValidationError> {
+        T::validate(self)
+    }
+}
+
+impl<T> Validate for Vec<T>
+where
+    T: Validate,
+{
+    fn validate(&self) -> Result<(), ValidationError> {
+        for (idx, value) in self.iter().enumerate() {
+            value
+                .validate()
+                .map_err(|err| err.in_field(&idx.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Converts result of validator function into validation error for the field.
+/// Empty `field` refers to the value itself.
+pub fn validate_with<E>(result: Result<(), E>, field: &str) -> Result<(), ValidationError>
+where
+    E: fmt::Display,
+{
+    result.map_err(|err| ValidationError::new(err).in_field(field))
+}
+
+/// Validates nested value of the field.
+pub fn validate_nested<T>(value: &T, field: &str) -> Result<(), ValidationError>
+where
+    T: Validate + ?Sized,
+{
+    value.validate().map_err(|err| err.in_field(field))
+}
+
+/// Validates the value after the layer is loaded.
+pub fn validated<T, E>(value: &T) -> Result<(), E>
+where
+    T: Validate + ?Sized,
+    E: Error,
+{
+    value.validate().map_err(E::custom)
+}
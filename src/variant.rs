@@ -0,0 +1,56 @@
+use serde::{
+    de::{value::BorrowedStrDeserializer, DeserializeSeed, EnumAccess, VariantAccess, Visitor},
+    forward_to_deserialize_any, Deserializer,
+};
+
+/// Deserializer that replays enum variant which identifier was already consumed.
+/// Used to deserialize fresh value when next layer names different variant.
+pub struct VariantDeserializer<A> {
+    variant: &'static str,
+    access: A,
+}
+
+impl<A> VariantDeserializer<A> {
+    #[inline]
+    pub fn new(variant: &'static str, access: A) -> Self {
+        VariantDeserializer { variant, access }
+    }
+}
+
+impl<'de, A> Deserializer<'de> for VariantDeserializer<A>
+where
+    A: VariantAccess<'de>,
+{
+    type Error = A::Error;
+
+    #[inline]
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, A::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de, A> EnumAccess<'de> for VariantDeserializer<A>
+where
+    A: VariantAccess<'de>,
+{
+    type Error = A::Error;
+    type Variant = A;
+
+    #[inline]
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, A), A::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let value = seed.deserialize(BorrowedStrDeserializer::new(self.variant))?;
+        Ok((value, self.access))
+    }
+}
@@ -0,0 +1,110 @@
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Limits {
+    #[figa(max)]
+    timeout: u32,
+    #[figa(min)]
+    retries: i8,
+    #[figa(sum)]
+    workers: u8,
+    #[figa(max)]
+    ratio: f64,
+    #[figa(sum)]
+    weight: f32,
+}
+
+fn limits() -> Limits {
+    Limits {
+        timeout: 30,
+        retries: 3,
+        workers: 2,
+        ratio: 0.5,
+        weight: 1.0,
+    }
+}
+
+#[test]
+fn max_keeps_larger_value() {
+    let mut value = limits();
+    update(&mut value, r#"{"timeout":10,"ratio":0.75}"#).unwrap();
+    assert_eq!(value.timeout, 30);
+    assert_eq!(value.ratio, 0.75);
+
+    update(&mut value, r#"{"timeout":60,"ratio":0.25}"#).unwrap();
+    assert_eq!(value.timeout, 60);
+    assert_eq!(value.ratio, 0.75);
+}
+
+#[test]
+fn min_keeps_smaller_value() {
+    let mut value = limits();
+    update(&mut value, r#"{"retries":5}"#).unwrap();
+    assert_eq!(value.retries, 3);
+
+    update(&mut value, r#"{"retries":-1}"#).unwrap();
+    assert_eq!(value.retries, -1);
+}
+
+#[test]
+fn sum_adds_layers() {
+    let mut value = limits();
+    update(&mut value, r#"{"workers":3,"weight":0.5}"#).unwrap();
+    update(&mut value, r#"{"workers":4}"#).unwrap();
+    assert_eq!(value.workers, 9);
+    assert_eq!(value.weight, 1.5);
+}
+
+#[test]
+fn sum_overflow_is_error() {
+    let mut value = limits();
+    let err = update(&mut value, r#"{"workers":254}"#).unwrap_err();
+    assert!(err.to_string().contains("sum overflow"), "{}", err);
+    assert_eq!(value.workers, 2);
+
+    update(&mut value, r#"{"weight":3e38}"#).unwrap();
+    let err = update(&mut value, r#"{"weight":3e38}"#).unwrap_err();
+    assert!(err.to_string().contains("sum overflow"), "{}", err);
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Flags {
+    #[figa(any)]
+    verbose: bool,
+    #[figa(all)]
+    colors: bool,
+}
+
+#[test]
+fn any_keeps_true() {
+    let mut value = Flags {
+        verbose: false,
+        colors: true,
+    };
+    update(&mut value, r#"{"verbose":false}"#).unwrap();
+    assert!(!value.verbose);
+    update(&mut value, r#"{"verbose":true}"#).unwrap();
+    update(&mut value, r#"{"verbose":false}"#).unwrap();
+    assert!(value.verbose);
+}
+
+#[test]
+fn all_keeps_false() {
+    let mut value = Flags {
+        verbose: false,
+        colors: true,
+    };
+    update(&mut value, r#"{"colors":true}"#).unwrap();
+    assert!(value.colors);
+    update(&mut value, r#"{"colors":false}"#).unwrap();
+    update(&mut value, r#"{"colors":true}"#).unwrap();
+    assert!(!value.colors);
+}
@@ -0,0 +1,48 @@
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Config {
+    #[figa(append_unique)]
+    features: Vec<String>,
+    #[figa(append_unique = hash)]
+    ports: Vec<u16>,
+}
+
+fn config() -> Config {
+    serde_json::from_str(r#"{"features":["a","b"],"ports":[80,443]}"#).unwrap()
+}
+
+#[test]
+fn present_values_are_skipped() {
+    let mut value = config();
+    update(&mut value, r#"{"features":["b","c","a","d"]}"#).unwrap();
+    assert_eq!(value.features, ["a", "b", "c", "d"]);
+}
+
+#[test]
+fn duplicates_within_layer_are_skipped() {
+    let mut value = config();
+    update(
+        &mut value,
+        r#"{"features":["c","c"],"ports":[8080,8080,80]}"#,
+    )
+    .unwrap();
+    assert_eq!(value.features, ["a", "b", "c"]);
+    assert_eq!(value.ports, [80, 443, 8080]);
+}
+
+#[test]
+fn hash_variant_preserves_order() {
+    let mut value = config();
+    update(&mut value, r#"{"ports":[22,443,21]}"#).unwrap();
+    update(&mut value, r#"{"ports":[80,23]}"#).unwrap();
+    assert_eq!(value.ports, [80, 443, 22, 21, 23]);
+}
@@ -0,0 +1,83 @@
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Debug, Default, PartialEq, Deserialize, Figa)]
+struct History {
+    #[figa(append, max_len = 3)]
+    strict: Vec<u32>,
+    #[figa(append, max_len = 3, overflow = error)]
+    explicit: Vec<u32>,
+    #[figa(append, max_len = 3, overflow = keep_first)]
+    first: Vec<u32>,
+    #[figa(append, max_len = 3, overflow = keep_last)]
+    last: Vec<u32>,
+    #[figa(append, max_len = 5, overflow = keep_last)]
+    log: String,
+    #[figa(max_len = 4, overflow = keep_first)]
+    name: String,
+}
+
+#[test]
+fn within_limit_is_kept() {
+    let mut value = History::default();
+    update(&mut value, r#"{"strict":[1,2],"explicit":[1]}"#).unwrap();
+    update(&mut value, r#"{"strict":[3],"explicit":[2,3]}"#).unwrap();
+    assert_eq!(value.strict, [1, 2, 3]);
+    assert_eq!(value.explicit, [1, 2, 3]);
+}
+
+#[test]
+fn overflow_error_fails_layer() {
+    let mut value = History::default();
+    update(&mut value, r#"{"strict":[1,2]}"#).unwrap();
+    let err = update(&mut value, r#"{"strict":[3,4]}"#).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("field `strict` exceeds maximum length of 3"),
+        "{}",
+        err
+    );
+
+    let err = update(&mut value, r#"{"explicit":[1,2,3,4]}"#).unwrap_err();
+    assert!(err.to_string().contains("field `explicit`"), "{}", err);
+}
+
+#[test]
+fn keep_first_drops_new_values() {
+    let mut value = History::default();
+    update(&mut value, r#"{"first":[1,2]}"#).unwrap();
+    update(&mut value, r#"{"first":[3,4,5]}"#).unwrap();
+    assert_eq!(value.first, [1, 2, 3]);
+}
+
+#[test]
+fn keep_last_is_sliding_window() {
+    let mut value = History::default();
+    update(&mut value, r#"{"last":[1,2]}"#).unwrap();
+    update(&mut value, r#"{"last":[3,4]}"#).unwrap();
+    assert_eq!(value.last, [2, 3, 4]);
+    update(&mut value, r#"{"last":[5]}"#).unwrap();
+    assert_eq!(value.last, [3, 4, 5]);
+}
+
+#[test]
+fn string_is_bounded_by_bytes() {
+    let mut value = History::default();
+    update(&mut value, r#"{"log":"abc"}"#).unwrap();
+    update(&mut value, r#"{"log":"def"}"#).unwrap();
+    assert_eq!(value.log, "bcdef");
+
+    update(&mut value, r#"{"name":"abcdef"}"#).unwrap();
+    assert_eq!(value.name, "abcd");
+
+    // Truncation doesn't split characters.
+    update(&mut value, r#"{"name":"abcé"}"#).unwrap();
+    assert_eq!(value.name, "abc");
+}
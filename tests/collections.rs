@@ -0,0 +1,78 @@
+use std::collections::{BinaryHeap, LinkedList, VecDeque};
+
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Figa)]
+struct Stage {
+    name: String,
+    jobs: u32,
+}
+
+#[derive(Debug, Deserialize, Figa)]
+struct Pipeline {
+    queue: VecDeque<u32>,
+    #[figa(append)]
+    hooks: LinkedList<String>,
+    priorities: BinaryHeap<u8>,
+    #[figa(update)]
+    stages: VecDeque<Stage>,
+}
+
+fn pipeline() -> Pipeline {
+    serde_json::from_str(
+        r#"{
+            "queue": [1, 2],
+            "hooks": ["pre"],
+            "priorities": [3, 1],
+            "stages": [{"name": "build", "jobs": 1}]
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn collections_are_appended() {
+    let mut value = pipeline();
+    update(
+        &mut value,
+        r#"{"queue":[3],"hooks":["post"],"priorities":[5,2]}"#,
+    )
+    .unwrap();
+    assert_eq!(value.queue, [1, 2, 3]);
+    assert_eq!(
+        value.hooks,
+        LinkedList::from(["pre".to_owned(), "post".to_owned()])
+    );
+    assert_eq!(value.priorities.into_sorted_vec(), [1, 2, 3, 5]);
+}
+
+#[test]
+fn vec_deque_is_updated_element_wise() {
+    let mut value = pipeline();
+    update(
+        &mut value,
+        r#"{"stages":[{"jobs":4},{"name":"test","jobs":2}]}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        value.stages,
+        [
+            Stage {
+                name: "build".to_owned(),
+                jobs: 4,
+            },
+            Stage {
+                name: "test".to_owned(),
+                jobs: 2,
+            },
+        ]
+    );
+}
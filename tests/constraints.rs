@@ -0,0 +1,212 @@
+use core::ops::Bound;
+
+use figa::{Constraint, ConstraintValue, Constraints, Validate};
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+#[serde(rename_all = "kebab-case")]
+struct Server {
+    #[figa(range = 1..=65535)]
+    port: u32,
+    #[figa(range = -5..u16::MAX as i32)]
+    backlog: Option<i32>,
+    #[figa(len = 1..)]
+    host_name: Option<String>,
+    #[figa(one_of = ["tcp", "udp"])]
+    protocol: String,
+}
+
+fn server() -> Server {
+    Server {
+        port: 80,
+        backlog: None,
+        host_name: None,
+        protocol: "tcp".to_owned(),
+    }
+}
+
+#[test]
+fn optional_fields_check_contained_value() {
+    let mut value = server();
+    update(&mut value, r#"{"backlog":10,"host-name":"a"}"#).unwrap();
+    assert_eq!(value.backlog, Some(10));
+    assert_eq!(value.host_name.as_deref(), Some("a"));
+
+    let err = update(&mut value, r#"{"backlog":-6}"#).unwrap_err();
+    assert!(
+        err.to_string().contains("field `backlog` is invalid"),
+        "{}",
+        err
+    );
+
+    let err = update(&mut value, r#"{"host-name":""}"#).unwrap_err();
+    assert!(
+        err.to_string().contains("field `host-name` is invalid"),
+        "{}",
+        err
+    );
+
+    value.backlog = Some(-10);
+    assert_eq!(value.validate().unwrap_err().path(), "backlog");
+    value.backlog = None;
+    value.host_name = None;
+    value.validate().unwrap();
+}
+
+#[test]
+fn failed_constraint_keeps_value() {
+    #[derive(Debug, PartialEq, Deserialize, Figa)]
+    struct D {
+        #[figa(range = 0..5)]
+        x: u32,
+        #[figa(append, len = ..=2)]
+        tags: Vec<String>,
+    }
+
+    let mut value = D {
+        x: 1,
+        tags: vec!["a".to_owned()],
+    };
+    assert!(update(&mut value, r#"{"x":7}"#).is_err());
+    assert!(update(&mut value, r#"{"tags":["b","c"]}"#).is_err());
+    assert_eq!(
+        value,
+        D {
+            x: 1,
+            tags: vec!["a".to_owned()],
+        }
+    );
+}
+
+#[test]
+fn constraints_are_listed() {
+    let constraints = Server::constraints();
+    assert_eq!(constraints.len(), 4);
+
+    assert_eq!(constraints[0].variant(), None);
+    assert_eq!(constraints[0].field(), "port");
+    assert_eq!(
+        *constraints[0].constraint(),
+        Constraint::Range {
+            start: Bound::Included(ConstraintValue::Int(1)),
+            end: Bound::Included(ConstraintValue::Int(65535)),
+        }
+    );
+    assert_eq!(
+        *constraints[1].constraint(),
+        Constraint::Range {
+            start: Bound::Included(ConstraintValue::Int(-5)),
+            end: Bound::Excluded(ConstraintValue::Int(65535)),
+        }
+    );
+    assert_eq!(constraints[2].field(), "host-name");
+    assert_eq!(
+        *constraints[2].constraint(),
+        Constraint::Len {
+            start: Bound::Included(1),
+            end: Bound::Unbounded,
+        }
+    );
+    assert_eq!(
+        *constraints[3].constraint(),
+        Constraint::OneOf(vec![
+            ConstraintValue::Str("tcp".to_owned()),
+            ConstraintValue::Str("udp".to_owned()),
+        ])
+    );
+}
+
+#[test]
+fn constraint_values_have_field_type() {
+    #[derive(Debug, PartialEq, Deserialize, Figa)]
+    struct Tuning {
+        #[figa(range = 0.0..=1.0)]
+        ratio: f64,
+        #[figa(range = 1..=10_000_000_000)]
+        limit: Option<u64>,
+        #[figa(one_of = [1, 2, 4])]
+        threads: u8,
+        #[figa(one_of = ['a', 'b'])]
+        mode: char,
+    }
+
+    let constraints = Tuning::constraints();
+    assert_eq!(
+        *constraints[0].constraint(),
+        Constraint::Range {
+            start: Bound::Included(ConstraintValue::Float(0.0)),
+            end: Bound::Included(ConstraintValue::Float(1.0)),
+        }
+    );
+    assert_eq!(
+        *constraints[1].constraint(),
+        Constraint::Range {
+            start: Bound::Included(ConstraintValue::Int(1)),
+            end: Bound::Included(ConstraintValue::Int(10_000_000_000)),
+        }
+    );
+    assert_eq!(
+        *constraints[2].constraint(),
+        Constraint::OneOf(vec![
+            ConstraintValue::Int(1),
+            ConstraintValue::Int(2),
+            ConstraintValue::Int(4),
+        ])
+    );
+    assert_eq!(
+        *constraints[3].constraint(),
+        Constraint::OneOf(vec![ConstraintValue::Char('a'), ConstraintValue::Char('b')])
+    );
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+enum Listener {
+    Tcp {
+        #[figa(range = ..1024)]
+        port: u16,
+    },
+    Unix(#[figa(len = ..=108)] String),
+}
+
+#[test]
+fn enum_constraints_name_variant() {
+    let constraints = Listener::constraints();
+    assert_eq!(constraints.len(), 2);
+    assert_eq!(constraints[0].variant(), Some("Tcp"));
+    assert_eq!(constraints[0].field(), "port");
+    assert_eq!(
+        *constraints[0].constraint(),
+        Constraint::Range {
+            start: Bound::Unbounded,
+            end: Bound::Excluded(ConstraintValue::Int(1024)),
+        }
+    );
+    assert_eq!(constraints[1].variant(), Some("Unix"));
+    assert_eq!(constraints[1].field(), "0");
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn pattern_is_listed() {
+    #[derive(Deserialize, Figa)]
+    struct Named {
+        #[figa(pattern = "^[a-z]+$")]
+        name: Option<String>,
+    }
+
+    let mut value = Named { name: None };
+    update(&mut value, r#"{"name":"abc"}"#).unwrap();
+    assert!(update(&mut value, r#"{"name":"A"}"#).is_err());
+    assert_eq!(
+        *Named::constraints()[0].constraint(),
+        Constraint::Pattern("^[a-z]+$")
+    );
+}
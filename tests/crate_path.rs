@@ -0,0 +1,56 @@
+//! Derive used through a facade crate that re-exports `figa` under another name.
+
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+/// Shadows `figa` crate, so generated code must use the path from `#[figa(crate)]`.
+#[allow(dead_code)]
+mod figa {}
+
+mod facade {
+    pub use ::figa as config;
+}
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: facade::config::Figa,
+{
+    facade::config::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+#[figa(crate = "crate::facade::config")]
+struct Server {
+    port: u16,
+    #[figa(append)]
+    hosts: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+#[figa(crate = "crate::facade::config")]
+enum Mode {
+    Fast { level: u8 },
+    Slow,
+}
+
+#[test]
+fn derive_uses_reexported_crate() {
+    let mut value = Server {
+        port: 1,
+        hosts: vec!["a".to_owned()],
+    };
+    update(&mut value, r#"{"port":2,"hosts":["b"]}"#).unwrap();
+    assert_eq!(
+        value,
+        Server {
+            port: 2,
+            hosts: vec!["a".to_owned(), "b".to_owned()],
+        }
+    );
+
+    let mut mode = Mode::Fast { level: 1 };
+    update(&mut mode, r#"{"Fast":{"level":2}}"#).unwrap();
+    assert_eq!(mode, Mode::Fast { level: 2 });
+    update(&mut mode, r#""Slow""#).unwrap();
+    assert_eq!(mode, Mode::Slow);
+}
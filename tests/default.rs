@@ -0,0 +1,69 @@
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+#[figa(default)]
+struct Config {
+    #[figa(default = 8080)]
+    port: u16,
+    #[figa(locked)]
+    user: Option<String>,
+    #[figa(default = true, locked)]
+    verify: bool,
+    #[figa(default = Some(3), locked = ignore)]
+    retries: Option<u32>,
+}
+
+#[test]
+fn default_is_first_layer() {
+    let mut value = Config::default();
+    assert_eq!(
+        value,
+        Config {
+            port: 8080,
+            user: None,
+            verify: true,
+            retries: Some(3),
+        }
+    );
+
+    update(&mut value, r#"{"port":1}"#).unwrap();
+    assert_eq!(value.port, 1);
+}
+
+#[test]
+fn locked_option_is_set_by_first_layer_over_default() {
+    let mut value = Config::default();
+    update(&mut value, r#"{"user":"root"}"#).unwrap();
+    assert_eq!(value.user.as_deref(), Some("root"));
+
+    let err = update(&mut value, r#"{"user":"other"}"#).unwrap_err();
+    assert!(
+        err.to_string().contains("field `user` is locked"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn locked_field_keeps_default_value() {
+    let mut value = Config::default();
+    let err = update(&mut value, r#"{"verify":false}"#).unwrap_err();
+    assert!(
+        err.to_string().contains("field `verify` is locked"),
+        "{}",
+        err
+    );
+    assert!(value.verify);
+
+    update(&mut value, r#"{"retries":5,"port":1}"#).unwrap();
+    assert_eq!(value.retries, Some(3));
+    assert_eq!(value.port, 1);
+}
@@ -0,0 +1,217 @@
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn load<T>(json: &str) -> T
+where
+    T: serde::de::DeserializeOwned,
+{
+    serde_json::from_str(json).unwrap()
+}
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+enum External {
+    Remote {
+        url: String,
+        #[figa(append)]
+        mirrors: Vec<String>,
+    },
+    Local(String),
+    Memory,
+}
+
+#[test]
+fn external_same_variant_updates_fields() {
+    let mut value: External = load(r#"{"Remote":{"url":"a","mirrors":["m1"]}}"#);
+    update(&mut value, r#"{"Remote":{"mirrors":["m2"]}}"#).unwrap();
+    assert_eq!(
+        value,
+        External::Remote {
+            url: "a".to_owned(),
+            mirrors: vec!["m1".to_owned(), "m2".to_owned()],
+        }
+    );
+}
+
+#[test]
+fn external_other_variant_replaces_value() {
+    let mut value: External = load(r#"{"Remote":{"url":"a","mirrors":[]}}"#);
+    update(&mut value, r#"{"Local":"/tmp"}"#).unwrap();
+    assert_eq!(value, External::Local("/tmp".to_owned()));
+
+    update(&mut value, r#""Memory""#).unwrap();
+    assert_eq!(value, External::Memory);
+}
+
+#[test]
+fn external_other_variant_must_be_complete() {
+    let mut value: External = load(r#""Memory""#);
+    assert!(update(&mut value, r#"{"Remote":{"mirrors":[]}}"#).is_err());
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+#[serde(tag = "type")]
+enum Internal {
+    Postgres { host: String, port: u16 },
+    Sqlite { path: String },
+    Memory,
+}
+
+#[test]
+fn internal_same_variant_updates_fields() {
+    let mut value: Internal = load(r#"{"type":"Postgres","host":"h","port":1}"#);
+    update(&mut value, r#"{"type":"Postgres","port":2}"#).unwrap();
+    assert_eq!(
+        value,
+        Internal::Postgres {
+            host: "h".to_owned(),
+            port: 2,
+        }
+    );
+}
+
+#[test]
+fn internal_missing_tag_updates_active_variant() {
+    let mut value: Internal = load(r#"{"type":"Postgres","host":"h","port":1}"#);
+    update(&mut value, r#"{"host":"g"}"#).unwrap();
+    assert_eq!(
+        value,
+        Internal::Postgres {
+            host: "g".to_owned(),
+            port: 1,
+        }
+    );
+}
+
+#[test]
+fn internal_other_variant_replaces_value() {
+    let mut value: Internal = load(r#"{"type":"Postgres","host":"h","port":1}"#);
+    update(&mut value, r#"{"type":"Sqlite","path":"db"}"#).unwrap();
+    assert_eq!(
+        value,
+        Internal::Sqlite {
+            path: "db".to_owned(),
+        }
+    );
+
+    update(&mut value, r#"{"type":"Memory"}"#).unwrap();
+    assert_eq!(value, Internal::Memory);
+
+    update(&mut value, r#"{"type":"Memory"}"#).unwrap();
+    assert_eq!(value, Internal::Memory);
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+#[serde(tag = "t", content = "c")]
+enum Adjacent {
+    Tcp { host: String, port: u16 },
+    Unix(String),
+    Off,
+}
+
+#[test]
+fn adjacent_same_variant_updates_fields() {
+    let mut value: Adjacent = load(r#"{"t":"Tcp","c":{"host":"h","port":1}}"#);
+    update(&mut value, r#"{"t":"Tcp","c":{"port":2}}"#).unwrap();
+    assert_eq!(
+        value,
+        Adjacent::Tcp {
+            host: "h".to_owned(),
+            port: 2,
+        }
+    );
+}
+
+#[test]
+fn adjacent_missing_tag_updates_active_variant() {
+    let mut value: Adjacent = load(r#"{"t":"Tcp","c":{"host":"h","port":1}}"#);
+    update(&mut value, r#"{"c":{"host":"g"}}"#).unwrap();
+    assert_eq!(
+        value,
+        Adjacent::Tcp {
+            host: "g".to_owned(),
+            port: 1,
+        }
+    );
+}
+
+#[test]
+fn adjacent_other_variant_replaces_value() {
+    let mut value: Adjacent = load(r#"{"t":"Tcp","c":{"host":"h","port":1}}"#);
+    update(&mut value, r#"{"t":"Unix","c":"/run/sock"}"#).unwrap();
+    assert_eq!(value, Adjacent::Unix("/run/sock".to_owned()));
+
+    update(&mut value, r#"{"t":"Off"}"#).unwrap();
+    assert_eq!(value, Adjacent::Off);
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Figa)]
+#[serde(untagged)]
+enum Untagged {
+    A { x: u32, y: u32 },
+    B { x: u32 },
+    C(String),
+    D,
+}
+
+#[test]
+fn untagged_partial_update_keeps_active_variant() {
+    let mut value: Untagged = load(r#"{"x":1,"y":2}"#);
+    update(&mut value, r#"{"x":5}"#).unwrap();
+    assert_eq!(value, Untagged::A { x: 5, y: 2 });
+}
+
+#[test]
+fn untagged_other_variant_replaces_value() {
+    let mut value: Untagged = load(r#"{"x":1}"#);
+    assert_eq!(value, Untagged::B { x: 1 });
+
+    update(&mut value, r#"{"x":2,"y":3}"#).unwrap();
+    assert_eq!(value, Untagged::A { x: 2, y: 3 });
+
+    update(&mut value, r#""s""#).unwrap();
+    assert_eq!(value, Untagged::C("s".to_owned()));
+
+    update(&mut value, "null").unwrap();
+    assert_eq!(value, Untagged::D);
+
+    update(&mut value, r#"{"x":4}"#).unwrap();
+    assert_eq!(value, Untagged::B { x: 4 });
+}
+
+#[test]
+fn untagged_mismatch_is_error() {
+    let mut value: Untagged = load(r#"{"x":1}"#);
+    assert!(update(&mut value, "[1, 2]").is_err());
+    assert_eq!(value, Untagged::B { x: 1 });
+}
+
+#[test]
+fn untagged_failed_layer_keeps_value() {
+    let mut value: Untagged = load(r#"{"x":1,"y":2}"#);
+    assert!(update(&mut value, r#"{"y":3,"x":"bad"}"#).is_err());
+    assert_eq!(value, Untagged::A { x: 1, y: 2 });
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Figa)]
+#[serde(untagged)]
+enum Either<T> {
+    Value(T),
+    List(Vec<T>),
+}
+
+#[test]
+fn untagged_generic_updates_active_variant() {
+    let mut value: Either<u32> = load("[1]");
+    update(&mut value, "[2]").unwrap();
+    assert_eq!(value, Either::List(vec![1, 2]));
+
+    update(&mut value, "3").unwrap();
+    assert_eq!(value, Either::Value(3));
+}
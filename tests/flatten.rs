@@ -0,0 +1,103 @@
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Tls {
+    cert: String,
+    #[figa(append)]
+    ciphers: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Retry {
+    attempts: u32,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Server {
+    port: u16,
+    #[serde(flatten)]
+    #[figa(flatten)]
+    tls: Tls,
+    #[serde(flatten)]
+    #[figa(flatten)]
+    retry: Retry,
+}
+
+fn server() -> Server {
+    serde_json::from_str(r#"{"port":1,"cert":"a","ciphers":["x"],"attempts":1}"#).unwrap()
+}
+
+#[test]
+fn flattened_fields_take_their_keys() {
+    let mut value = server();
+    update(&mut value, r#"{"ciphers":["y"],"attempts":3,"port":2}"#).unwrap();
+    assert_eq!(
+        value,
+        Server {
+            port: 2,
+            tls: Tls {
+                cert: "a".to_owned(),
+                ciphers: vec!["x".to_owned(), "y".to_owned()],
+            },
+            retry: Retry { attempts: 3 },
+        }
+    );
+}
+
+#[test]
+fn unknown_key_is_error() {
+    let mut value = server();
+    let err = update(&mut value, r#"{"bogus":1}"#).unwrap_err();
+    assert!(err.to_string().contains("unknown field `bogus`"), "{}", err);
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Leaf {
+    x: u32,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Mid {
+    #[serde(flatten)]
+    #[figa(flatten)]
+    leaf: Leaf,
+    y: u32,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Outer {
+    #[serde(flatten)]
+    #[figa(flatten)]
+    mid: Mid,
+    #[serde(flatten)]
+    #[figa(flatten)]
+    retry: Retry,
+}
+
+#[test]
+fn nested_flatten_takes_only_its_keys() {
+    let mut value: Outer = serde_json::from_str(r#"{"x":1,"y":2,"attempts":3}"#).unwrap();
+    update(&mut value, r#"{"attempts":4}"#).unwrap();
+    update(&mut value, r#"{"x":5,"attempts":6}"#).unwrap();
+    assert_eq!(
+        value,
+        Outer {
+            mid: Mid {
+                leaf: Leaf { x: 5 },
+                y: 2,
+            },
+            retry: Retry { attempts: 6 },
+        }
+    );
+
+    let err = update(&mut value, r#"{"z":1}"#).unwrap_err();
+    assert!(err.to_string().contains("unknown field `z`"), "{}", err);
+}
@@ -0,0 +1,73 @@
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Section<A> {
+    name: String,
+    value: A,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Pair<D, E>(D, E);
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Marker<E> {
+    #[serde(skip)]
+    #[figa(skip)]
+    marker: Option<E>,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+#[serde(tag = "kind")]
+enum Choice<A, D> {
+    Left { value: A },
+    Right { value: D },
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Figa)]
+#[serde(untagged)]
+enum Either<A, D> {
+    Left(A),
+    Right(D),
+}
+
+#[test]
+fn generic_params_do_not_clash() {
+    let mut section = Section {
+        name: "a".to_owned(),
+        value: Section {
+            name: "b".to_owned(),
+            value: 1u32,
+        },
+    };
+    update(&mut section, r#"{"value":{"value":2}}"#).unwrap();
+    assert_eq!(section.name, "a");
+    assert_eq!(section.value.value, 2);
+
+    let mut pair = Pair(1u8, true);
+    update(&mut pair, "[2, false]").unwrap();
+    assert_eq!(pair, Pair(2, false));
+
+    let mut marker = Marker::<u8> { marker: None };
+    update(&mut marker, "{}").unwrap();
+    assert_eq!(marker, Marker { marker: None });
+
+    let mut choice = Choice::<u8, bool>::Left { value: 1 };
+    update(&mut choice, r#"{"value":2}"#).unwrap();
+    assert_eq!(choice, Choice::Left { value: 2 });
+    update(&mut choice, r#"{"kind":"Right","value":true}"#).unwrap();
+    assert_eq!(choice, Choice::Right { value: true });
+
+    let mut either = Either::<u8, String>::Left(1);
+    update(&mut either, "2").unwrap();
+    assert_eq!(either, Either::Left(2));
+    update(&mut either, r#""x""#).unwrap();
+    assert_eq!(either, Either::Right("x".to_owned()));
+}
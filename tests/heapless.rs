@@ -0,0 +1,73 @@
+#![cfg(feature = "heapless")]
+
+use figa_proc::Figa;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Debug, Default, Figa)]
+struct Config {
+    name: heapless::String<4>,
+    ports: heapless::Vec<u16, 3>,
+}
+
+fn config() -> Config {
+    let mut value = Config::default();
+    update(&mut value, r#"{"name":"ab","ports":[1]}"#).unwrap();
+    value
+}
+
+#[test]
+fn string_is_replaced() {
+    let mut value = config();
+    update(&mut value, r#"{"name":"cdef"}"#).unwrap();
+    assert_eq!(value.name, "cdef");
+}
+
+#[test]
+fn string_overflow_keeps_value() {
+    let mut value = config();
+    let err = update(&mut value, r#"{"name":"cdefg"}"#).unwrap_err();
+    assert!(err.to_string().contains("at most 4 bytes"), "{}", err);
+    assert_eq!(value.name, "ab");
+}
+
+#[test]
+fn vec_is_appended() {
+    let mut value = config();
+    update(&mut value, r#"{"ports":[2,3]}"#).unwrap();
+    assert_eq!(value.ports, [1, 2, 3]);
+}
+
+#[test]
+fn vec_overflow_keeps_value() {
+    let mut value = config();
+    let err = update(&mut value, r#"{"ports":[2,3,4]}"#).unwrap_err();
+    assert!(err.to_string().contains("at most 3 elements"), "{}", err);
+    assert_eq!(value.ports, [1]);
+}
+
+#[test]
+fn vec_invalid_element_keeps_value() {
+    let mut value = config();
+    assert!(update(&mut value, r#"{"ports":[2,"x"]}"#).is_err());
+    assert_eq!(value.ports, [1]);
+}
+
+#[test]
+fn element_wise_overflow_drops_pushed_elements() {
+    #[derive(Debug, Default, Figa)]
+    struct Hosts {
+        #[figa(update)]
+        ports: heapless::Vec<u16, 2>,
+    }
+
+    let mut value = Hosts::default();
+    update(&mut value, r#"{"ports":[1]}"#).unwrap();
+    assert!(update(&mut value, r#"{"ports":[5,6,7]}"#).is_err());
+    assert_eq!(value.ports.len(), 1);
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "indexmap")]
+
+use std::collections::hash_map::RandomState;
+
+use figa_proc::Figa;
+
+type IndexMap<K, V> = indexmap::IndexMap<K, V, RandomState>;
+type IndexSet<T> = indexmap::IndexSet<T, RandomState>;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Debug, Default, Figa)]
+struct Routes {
+    paths: IndexMap<String, u16>,
+    names: IndexSet<String>,
+    #[figa(update)]
+    limits: IndexMap<String, Option<u32>>,
+}
+
+fn routes() -> Routes {
+    let mut value = Routes::default();
+    update(
+        &mut value,
+        r#"{"paths":{"b":1,"a":2},"names":["y","x"],"limits":{"b":null,"a":1}}"#,
+    )
+    .unwrap();
+    value
+}
+
+#[test]
+fn order_is_preserved() {
+    let mut value = routes();
+    update(&mut value, r#"{"paths":{"c":3,"b":4},"names":["z","y"]}"#).unwrap();
+    assert_eq!(
+        value.paths.into_iter().collect::<Vec<_>>(),
+        [
+            ("b".to_owned(), 4),
+            ("a".to_owned(), 2),
+            ("c".to_owned(), 3)
+        ]
+    );
+    assert_eq!(value.names.into_iter().collect::<Vec<_>>(), ["y", "x", "z"]);
+}
+
+#[test]
+fn update_keeps_key_position() {
+    let mut value = routes();
+    update(&mut value, r#"{"limits":{"c":3,"b":2}}"#).unwrap();
+    assert_eq!(
+        value.limits.into_iter().collect::<Vec<_>>(),
+        [
+            ("b".to_owned(), Some(2)),
+            ("a".to_owned(), Some(1)),
+            ("c".to_owned(), Some(3)),
+        ]
+    );
+}
@@ -0,0 +1,60 @@
+#![cfg(any(feature = "smallvec", feature = "arrayvec"))]
+
+use figa_proc::Figa;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn smallvec_is_appended() {
+    #[derive(Debug, Default, Figa)]
+    struct Config {
+        ports: smallvec::SmallVec<[u16; 2]>,
+        #[figa(update)]
+        limits: smallvec::SmallVec<[Option<u32>; 2]>,
+    }
+
+    let mut value = Config::default();
+    update(&mut value, r#"{"ports":[1,2],"limits":[null,1]}"#).unwrap();
+    update(&mut value, r#"{"ports":[3],"limits":[2,null,3]}"#).unwrap();
+    assert_eq!(value.ports.as_slice(), [1, 2, 3]);
+    assert!(value.ports.spilled());
+    assert_eq!(value.limits.as_slice(), [Some(2), Some(1), Some(3)]);
+}
+
+#[cfg(feature = "arrayvec")]
+#[derive(Debug, Default, Figa)]
+struct Fixed {
+    ports: arrayvec::ArrayVec<u16, 3>,
+    #[figa(update)]
+    limits: arrayvec::ArrayVec<Option<u32>, 2>,
+}
+
+#[cfg(feature = "arrayvec")]
+#[test]
+fn arrayvec_is_appended() {
+    let mut value = Fixed::default();
+    update(&mut value, r#"{"ports":[1],"limits":[null]}"#).unwrap();
+    update(&mut value, r#"{"ports":[2,3],"limits":[1,2]}"#).unwrap();
+    assert_eq!(value.ports.as_slice(), [1, 2, 3]);
+    assert_eq!(value.limits.as_slice(), [Some(1), Some(2)]);
+}
+
+#[cfg(feature = "arrayvec")]
+#[test]
+fn arrayvec_overflow_keeps_value() {
+    let mut value = Fixed::default();
+    update(&mut value, r#"{"ports":[1],"limits":[1]}"#).unwrap();
+
+    let err = update(&mut value, r#"{"ports":[2,3,4]}"#).unwrap_err();
+    assert!(err.to_string().contains("at most 3 elements"), "{}", err);
+    assert_eq!(value.ports.as_slice(), [1]);
+
+    assert!(update(&mut value, r#"{"limits":[null,2,3]}"#).is_err());
+    assert_eq!(value.limits.len(), 1);
+}
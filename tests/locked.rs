@@ -0,0 +1,78 @@
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Security {
+    #[figa(locked)]
+    verify: bool,
+    #[figa(locked = ignore)]
+    ca: String,
+    #[figa(locked)]
+    admin: Option<String>,
+    #[figa(locked, append)]
+    trusted: Option<Vec<String>>,
+    level: u8,
+}
+
+fn security() -> Security {
+    serde_json::from_str(r#"{"verify":true,"ca":"root.pem","admin":null,"trusted":null,"level":1}"#)
+        .unwrap()
+}
+
+#[test]
+fn locked_field_is_error() {
+    let mut value = security();
+    let err = update(&mut value, r#"{"level":2,"verify":false}"#).unwrap_err();
+    assert!(
+        err.to_string().contains("field `verify` is locked"),
+        "{}",
+        err
+    );
+    assert!(value.verify);
+}
+
+#[test]
+fn locked_ignore_keeps_value() {
+    let mut value = security();
+    update(&mut value, r#"{"ca":"other.pem","level":2}"#).unwrap();
+    assert_eq!(value.ca, "root.pem");
+    assert_eq!(value.level, 2);
+}
+
+#[test]
+fn layer_without_locked_field_loads() {
+    let mut value = security();
+    update(&mut value, r#"{"level":3}"#).unwrap();
+    assert_eq!(value.level, 3);
+}
+
+#[test]
+fn option_is_set_by_first_layer() {
+    let mut value = security();
+    update(&mut value, r#"{"admin":null}"#).unwrap();
+    update(&mut value, r#"{"admin":"root"}"#).unwrap();
+    assert_eq!(value.admin.as_deref(), Some("root"));
+
+    let err = update(&mut value, r#"{"admin":"other"}"#).unwrap_err();
+    assert!(
+        err.to_string().contains("field `admin` is locked"),
+        "{}",
+        err
+    );
+    assert_eq!(value.admin.as_deref(), Some("root"));
+}
+
+#[test]
+fn option_uses_own_strategy_until_set() {
+    let mut value = security();
+    update(&mut value, r#"{"trusted":["a"]}"#).unwrap();
+    assert_eq!(value.trusted, Some(vec!["a".to_owned()]));
+    assert!(update(&mut value, r#"{"trusted":["b"]}"#).is_err());
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "std")]
+
+use std::path::{Path, PathBuf};
+
+use figa::{path::with_origin, RelativePath};
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Debug, Deserialize, Figa)]
+struct Config {
+    #[figa(path)]
+    data: PathBuf,
+    #[figa(path)]
+    cache: Option<PathBuf>,
+    log: RelativePath,
+}
+
+fn config() -> Config {
+    with_origin(Path::new("/etc/app/base.json"), || {
+        serde_json::from_str(r#"{"data":"/var/data","cache":null,"log":"app.log"}"#).unwrap()
+    })
+}
+
+#[test]
+fn relative_path_is_resolved_against_origin() {
+    let value = config();
+    assert_eq!(value.log.as_path(), Path::new("/etc/app/app.log"));
+
+    let mut value = value;
+    with_origin(Path::new("/home/user/.app/override.json"), || {
+        update(
+            &mut value,
+            r#"{"data":"data","cache":"cache","log":"logs/app.log"}"#,
+        )
+    })
+    .unwrap();
+
+    assert_eq!(value.data, Path::new("/home/user/.app/data"));
+    assert_eq!(
+        value.cache.as_deref(),
+        Some(Path::new("/home/user/.app/cache"))
+    );
+    assert_eq!(
+        value.log.as_path(),
+        Path::new("/home/user/.app/logs/app.log")
+    );
+}
+
+#[test]
+fn absolute_path_is_kept() {
+    let mut value = config();
+    with_origin(Path::new("/home/user/override.json"), || {
+        update(
+            &mut value,
+            r#"{"data":"/srv/data","log":"/var/log/app.log"}"#,
+        )
+    })
+    .unwrap();
+    assert_eq!(value.data, Path::new("/srv/data"));
+    assert_eq!(value.log.as_path(), Path::new("/var/log/app.log"));
+}
+
+#[test]
+fn path_without_origin_is_kept() {
+    let mut value = config();
+    update(&mut value, r#"{"data":"data"}"#).unwrap();
+    assert_eq!(value.data, Path::new("data"));
+}
+
+#[test]
+fn origin_is_restored_after_nested_call() {
+    let mut value = config();
+    with_origin(Path::new("/a/outer.json"), || {
+        with_origin(Path::new("/b/inner.json"), || {
+            update(&mut value, r#"{"data":"x"}"#)
+        })
+        .unwrap();
+        update(&mut value, r#"{"cache":"y"}"#)
+    })
+    .unwrap();
+    assert_eq!(value.data, Path::new("/b/x"));
+    assert_eq!(value.cache.as_deref(), Some(Path::new("/a/y")));
+}
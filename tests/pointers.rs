@@ -0,0 +1,103 @@
+use std::{borrow::Cow, rc::Rc, sync::Arc};
+
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Figa)]
+struct Host {
+    name: String,
+    port: u16,
+}
+
+fn host(name: &str, port: u16) -> Host {
+    Host {
+        name: name.to_owned(),
+        port,
+    }
+}
+
+#[derive(Debug, PartialEq, Figa)]
+struct Shared {
+    #[figa(update)]
+    boxed: Box<Host>,
+    #[figa(update)]
+    rc: Rc<Vec<Host>>,
+    #[figa(update)]
+    arc: Arc<Host>,
+    #[figa(update)]
+    cow: Cow<'static, [u16]>,
+}
+
+#[test]
+fn update_passes_through_smart_pointers() {
+    let hosts = Rc::new(vec![host("a", 1)]);
+    let arc = Arc::new(host("b", 2));
+    let mut value = Shared {
+        boxed: Box::new(host("a", 1)),
+        rc: hosts.clone(),
+        arc: arc.clone(),
+        cow: Cow::Borrowed(&[1, 2]),
+    };
+
+    update(
+        &mut value,
+        r#"{"boxed":{"port":2},"rc":[{"port":3}],"arc":{"port":4},"cow":[5]}"#,
+    )
+    .unwrap();
+    assert_eq!(*value.boxed, host("a", 2));
+    assert_eq!(*value.rc, vec![host("a", 3)]);
+    assert_eq!(*value.arc, host("b", 4));
+    assert_eq!(*value.cow, [5, 2]);
+
+    // Shared snapshots are not changed.
+    assert_eq!(*hosts, vec![host("a", 1)]);
+    assert_eq!(*arc, host("b", 2));
+}
+
+#[derive(Debug, PartialEq, Figa)]
+struct Defaults {
+    limit: Box<u32>,
+    tags: Rc<Vec<String>>,
+    name: Cow<'static, str>,
+}
+
+#[test]
+fn default_strategy_passes_through() {
+    let tags = Rc::new(vec!["a".to_owned()]);
+    let mut value = Defaults {
+        limit: Box::new(1),
+        tags: tags.clone(),
+        name: Cow::Borrowed("x"),
+    };
+    update(&mut value, r#"{"limit":2,"tags":["b"],"name":"y"}"#).unwrap();
+    assert_eq!(*value.limit, 2);
+    assert_eq!(*value.tags, ["a", "b"]);
+    assert_eq!(value.name, "y");
+    assert_eq!(*tags, ["a"]);
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Node {
+    value: u32,
+    #[figa(update)]
+    next: Option<Box<Node>>,
+}
+
+#[test]
+fn recursive_box_is_updated() {
+    let mut value: Node =
+        serde_json::from_str(r#"{"value":1,"next":{"value":2,"next":null}}"#).unwrap();
+    update(&mut value, r#"{"next":{"value":3}}"#).unwrap();
+    assert_eq!(value.value, 1);
+    assert_eq!(value.next.as_ref().unwrap().value, 3);
+
+    update(&mut value, r#"{"next":{"next":{"value":4,"next":null}}}"#).unwrap();
+    assert_eq!(value.next.unwrap().next.unwrap().value, 4);
+}
@@ -0,0 +1,110 @@
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn load<T>(json: &str) -> T
+where
+    T: serde::de::DeserializeOwned,
+{
+    serde_json::from_str(json).unwrap()
+}
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+#[serde(rename_all = "kebab-case")]
+struct Server {
+    listen_port: u16,
+    #[serde(rename = "host")]
+    host_name: String,
+    #[serde(alias = "worker-count", alias = "threads")]
+    workers: u32,
+    r#type: String,
+}
+
+#[test]
+fn struct_accepts_serde_keys() {
+    let json = r#"{"listen-port":1,"host":"a","worker-count":2,"type":"t"}"#;
+    let mut value: Server = load(json);
+    update(
+        &mut value,
+        r#"{"listen-port":3,"host":"b","threads":4,"type":"u"}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        value,
+        load(r#"{"listen-port":3,"host":"b","workers":4,"type":"u"}"#)
+    );
+}
+
+#[test]
+fn struct_rejects_rust_names() {
+    let mut value: Server = load(r#"{"listen-port":1,"host":"a","workers":2,"type":"t"}"#);
+    assert!(update(&mut value, r#"{"listen_port":3}"#).is_err());
+    assert!(update(&mut value, r#"{"host_name":"b"}"#).is_err());
+    assert!(serde_json::from_str::<Server>(r#"{"listen_port":3}"#).is_err());
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+#[serde(rename_all = "snake_case", rename_all_fields = "camelCase")]
+enum Backend {
+    LocalDisk {
+        root_dir: String,
+    },
+    #[serde(rename = "s3", alias = "aws")]
+    S3Bucket {
+        bucket_name: String,
+        #[serde(rename = "region")]
+        aws_region: String,
+    },
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    Memory {
+        max_size: u64,
+    },
+}
+
+#[test]
+fn enum_accepts_serde_keys() {
+    let mut value: Backend = load(r#"{"local_disk":{"rootDir":"/a"}}"#);
+    update(&mut value, r#"{"local_disk":{"rootDir":"/b"}}"#).unwrap();
+    assert_eq!(value, load(r#"{"local_disk":{"rootDir":"/b"}}"#));
+
+    let mut value: Backend = load(r#"{"s3":{"bucketName":"x","region":"eu"}}"#);
+    update(&mut value, r#"{"aws":{"region":"us"}}"#).unwrap();
+    assert_eq!(value, load(r#"{"s3":{"bucketName":"x","region":"us"}}"#));
+
+    let mut value: Backend = load(r#"{"memory":{"MAX_SIZE":1}}"#);
+    update(&mut value, r#"{"memory":{"MAX_SIZE":2}}"#).unwrap();
+    assert_eq!(value, load(r#"{"memory":{"MAX_SIZE":2}}"#));
+}
+
+#[test]
+fn enum_rejects_rust_names() {
+    let mut value: Backend = load(r#"{"local_disk":{"rootDir":"/a"}}"#);
+    assert!(update(&mut value, r#"{"LocalDisk":{"rootDir":"/b"}}"#).is_err());
+    assert!(update(&mut value, r#"{"local_disk":{"root_dir":"/b"}}"#).is_err());
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum Auth {
+    Token {
+        #[serde(alias = "secret")]
+        value: String,
+    },
+    None,
+}
+
+#[test]
+fn tagged_enum_accepts_serde_keys() {
+    let mut value: Auth = load(r#"{"kind":"token","value":"a"}"#);
+    update(&mut value, r#"{"kind":"token","secret":"b"}"#).unwrap();
+    assert_eq!(value, load(r#"{"kind":"token","value":"b"}"#));
+
+    update(&mut value, r#"{"kind":"none"}"#).unwrap();
+    assert_eq!(value, Auth::None);
+}
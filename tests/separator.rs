@@ -0,0 +1,45 @@
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Debug, Default, PartialEq, Deserialize, Figa)]
+struct Env {
+    #[figa(append, separator = ":")]
+    path: String,
+    #[figa(append, separator = ", ")]
+    flags: String,
+    #[figa(append)]
+    plain: String,
+}
+
+#[test]
+fn separator_joins_layers() {
+    let mut value = Env::default();
+    update(&mut value, r#"{"path":"/bin","flags":"-O2"}"#).unwrap();
+    update(&mut value, r#"{"path":"/usr/bin","flags":"-g"}"#).unwrap();
+    assert_eq!(value.path, "/bin:/usr/bin");
+    assert_eq!(value.flags, "-O2, -g");
+}
+
+#[test]
+fn separator_skips_empty_strings() {
+    let mut value = Env::default();
+    update(&mut value, r#"{"path":""}"#).unwrap();
+    update(&mut value, r#"{"path":"/bin"}"#).unwrap();
+    update(&mut value, r#"{"path":""}"#).unwrap();
+    assert_eq!(value.path, "/bin");
+}
+
+#[test]
+fn append_without_separator_concatenates() {
+    let mut value = Env::default();
+    update(&mut value, r#"{"plain":"ab"}"#).unwrap();
+    update(&mut value, r#"{"plain":"cd"}"#).unwrap();
+    assert_eq!(value.plain, "abcd");
+}
@@ -0,0 +1,73 @@
+use std::cell::Cell;
+
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+/// Runtime state that has no `Deserialize` implementation.
+#[derive(Debug, Default)]
+struct Counter(Cell<u32>);
+
+#[derive(Debug, Deserialize, Figa)]
+struct Config {
+    name: String,
+    #[serde(skip)]
+    #[figa(skip)]
+    loads: Counter,
+    #[serde(skip)]
+    #[figa(skip)]
+    cached: Option<String>,
+}
+
+fn config() -> Config {
+    let mut value: Config = serde_json::from_str(r#"{"name":"a"}"#).unwrap();
+    value.loads.0.set(1);
+    value.cached = Some("x".to_owned());
+    value
+}
+
+#[test]
+fn skipped_fields_are_untouched() {
+    let mut value = config();
+    update(&mut value, r#"{"name":"b"}"#).unwrap();
+    assert_eq!(value.name, "b");
+    assert_eq!(value.loads.0.get(), 1);
+    assert_eq!(value.cached.as_deref(), Some("x"));
+}
+
+#[test]
+fn layer_with_skipped_field_fails() {
+    let mut value = config();
+    let err = update(&mut value, r#"{"cached":"y"}"#).unwrap_err();
+    assert!(
+        err.to_string().contains("unknown field `cached`"),
+        "{}",
+        err
+    );
+    assert_eq!(value.cached.as_deref(), Some("x"));
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+enum Mode {
+    Run {
+        jobs: u32,
+        #[serde(skip)]
+        #[figa(skip)]
+        pid: u32,
+    },
+    Idle,
+}
+
+#[test]
+fn skipped_variant_fields_are_untouched() {
+    let mut value = Mode::Run { jobs: 1, pid: 42 };
+    update(&mut value, r#"{"Run":{"jobs":2}}"#).unwrap();
+    assert_eq!(value, Mode::Run { jobs: 2, pid: 42 });
+    assert!(update(&mut value, r#"{"Run":{"pid":1}}"#).is_err());
+}
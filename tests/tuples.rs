@@ -0,0 +1,92 @@
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Figa)]
+struct Limit {
+    soft: u32,
+    hard: u32,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Config {
+    ports: [u16; 3],
+    range: (u32, u32),
+    #[figa(update)]
+    limits: [Limit; 2],
+    #[figa(update)]
+    pair: (Limit, String),
+}
+
+fn config() -> Config {
+    serde_json::from_str(
+        r#"{
+            "ports": [1, 2, 3],
+            "range": [10, 20],
+            "limits": [{"soft": 1, "hard": 2}, {"soft": 3, "hard": 4}],
+            "pair": [{"soft": 5, "hard": 6}, "a"]
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn full_sequence_updates_all_elements() {
+    let mut value = config();
+    update(
+        &mut value,
+        r#"{"ports":[4,5,6],"range":[30,40],"pair":[{"hard":7},"b"]}"#,
+    )
+    .unwrap();
+    assert_eq!(value.ports, [4, 5, 6]);
+    assert_eq!(value.range, (30, 40));
+    assert_eq!(value.pair, (Limit { soft: 5, hard: 7 }, "b".to_owned()));
+}
+
+#[test]
+fn shorter_sequence_updates_prefix() {
+    let mut value = config();
+    update(
+        &mut value,
+        r#"{"ports":[7],"range":[11],"limits":[{"hard":9}]}"#,
+    )
+    .unwrap();
+    assert_eq!(value.ports, [7, 2, 3]);
+    assert_eq!(value.range, (11, 20));
+    assert_eq!(
+        value.limits,
+        [Limit { soft: 1, hard: 9 }, Limit { soft: 3, hard: 4 }]
+    );
+}
+
+#[test]
+fn longer_array_is_error_and_keeps_value() {
+    let mut value = config();
+    let err = update(&mut value, r#"{"ports":[7,8,9,10]}"#).unwrap_err();
+    assert!(err.to_string().contains("invalid length 4"), "{}", err);
+    assert_eq!(value.ports, [1, 2, 3]);
+}
+
+#[test]
+fn longer_tuple_is_error_and_keeps_value() {
+    let mut value = config();
+    let err = update(&mut value, r#"{"range":[1,2,3]}"#).unwrap_err();
+    assert!(err.to_string().contains("invalid length 3"), "{}", err);
+    assert_eq!(value.range, (10, 20));
+}
+
+#[test]
+fn failed_element_keeps_earlier_elements() {
+    let mut value = config();
+    assert!(update(&mut value, r#"{"limits":[{"soft":8},{"hard":"x"}]}"#).is_err());
+    assert_eq!(
+        value.limits,
+        [Limit { soft: 1, hard: 2 }, Limit { soft: 3, hard: 4 }]
+    );
+}
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Figa)]
+struct Host {
+    name: String,
+    port: u16,
+}
+
+fn host(name: &str, port: u16) -> Host {
+    Host {
+        name: name.to_owned(),
+        port,
+    }
+}
+
+type Hosts = Vec<Host>;
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Cluster {
+    #[figa(update)]
+    hosts: Hosts,
+    #[figa(update)]
+    by_name: HashMap<String, Host>,
+    #[figa(update)]
+    primary: Option<Host>,
+    #[figa(update)]
+    ports: Vec<u16>,
+    #[figa(append)]
+    tags: Vec<String>,
+}
+
+fn cluster() -> Cluster {
+    Cluster {
+        hosts: vec![host("a", 1), host("b", 2)],
+        by_name: HashMap::from([("a".to_owned(), host("a", 1))]),
+        primary: None,
+        ports: vec![1, 2],
+        tags: vec!["x".to_owned()],
+    }
+}
+
+#[test]
+fn update_is_element_wise_through_alias() {
+    let mut value = cluster();
+    update(
+        &mut value,
+        r#"{"hosts":[{"port":10},{},{"name":"c","port":3}]}"#,
+    )
+    .unwrap();
+    assert_eq!(value.hosts, vec![host("a", 10), host("b", 2), host("c", 3)]);
+}
+
+#[test]
+fn update_is_element_wise_for_maps_and_options() {
+    let mut value = cluster();
+    update(
+        &mut value,
+        r#"{"by_name":{"a":{"port":5},"b":{"name":"b","port":6}},"ports":[7],"tags":["y"]}"#,
+    )
+    .unwrap();
+    assert_eq!(value.by_name["a"], host("a", 5));
+    assert_eq!(value.by_name["b"], host("b", 6));
+    assert_eq!(value.ports, vec![7, 2]);
+    assert_eq!(value.tags, vec!["x".to_owned(), "y".to_owned()]);
+
+    update(&mut value, r#"{"primary":{"name":"p","port":1}}"#).unwrap();
+    update(&mut value, r#"{"primary":{"port":2}}"#).unwrap();
+    assert_eq!(value.primary, Some(host("p", 2)));
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Manual(u32);
+
+impl figa::Figa for Manual {
+    fn update<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = <u32 as serde::Deserialize>::deserialize(deserializer)?;
+        self.0 += value;
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct UsesManual {
+    #[figa(update)]
+    manual: Manual,
+    #[figa(update)]
+    manuals: Vec<Manual>,
+}
+
+#[test]
+fn update_uses_handwritten_impl() {
+    let mut value = UsesManual {
+        manual: Manual(1),
+        manuals: vec![Manual(1)],
+    };
+    update(&mut value, r#"{"manual":2,"manuals":[3,4]}"#).unwrap();
+    assert_eq!(value.manual, Manual(3));
+    assert_eq!(value.manuals, vec![Manual(4), Manual(4)]);
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn update_is_element_wise_for_heapless_alias() {
+    #[derive(Figa)]
+    struct Table {
+        #[figa(update)]
+        hosts: heapless::FnvIndexMap<u8, Host, 4>,
+    }
+
+    let mut value = Table {
+        hosts: heapless::FnvIndexMap::new(),
+    };
+    value.hosts.insert(1, host("a", 1)).unwrap();
+
+    update(&mut value, r#"{"hosts":{"1":{"port":2}}}"#).unwrap();
+    assert_eq!(value.hosts[&1], host("a", 2));
+}
@@ -0,0 +1,134 @@
+use figa::Validate;
+use figa_proc::Figa;
+use serde_derive::Deserialize;
+
+fn update<T>(value: &mut T, json: &str) -> Result<(), serde_json::Error>
+where
+    T: figa::Figa,
+{
+    figa::Figa::update(value, &mut serde_json::Deserializer::from_str(json))
+}
+
+fn non_zero(port: &u16) -> Result<(), &'static str> {
+    if *port == 0 {
+        Err("port must not be zero")
+    } else {
+        Ok(())
+    }
+}
+
+fn ordered(limits: &Limits) -> Result<(), String> {
+    if limits.min <= limits.max {
+        Ok(())
+    } else {
+        Err(format!("min {} exceeds max {}", limits.min, limits.max))
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Backend {
+    #[figa(validate = "non_zero")]
+    port: u16,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+#[figa(validate = "ordered")]
+struct Limits {
+    min: u32,
+    max: u32,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Figa)]
+struct Config {
+    #[figa(validate)]
+    primary: Backend,
+    #[figa(update, validate)]
+    backends: Vec<Backend>,
+    #[figa(validate)]
+    limits: Limits,
+}
+
+fn config() -> Config {
+    Config {
+        primary: Backend { port: 80 },
+        backends: vec![Backend { port: 81 }],
+        limits: Limits { min: 1, max: 2 },
+    }
+}
+
+#[test]
+fn field_validator_is_called() {
+    let mut value = Backend { port: 1 };
+    value.validate().unwrap();
+
+    value.port = 0;
+    let err = value.validate().unwrap_err();
+    assert_eq!(err.path(), "port");
+    assert_eq!(err.message(), "port must not be zero");
+    assert_eq!(
+        err.to_string(),
+        "field `port` is invalid: port must not be zero"
+    );
+}
+
+#[test]
+fn nested_errors_have_field_path() {
+    let mut value = config();
+    value.validate().unwrap();
+
+    value.primary.port = 0;
+    assert_eq!(value.validate().unwrap_err().path(), "primary.port");
+
+    value.primary.port = 80;
+    value.backends.push(Backend { port: 0 });
+    assert_eq!(value.validate().unwrap_err().path(), "backends.1.port");
+}
+
+#[test]
+fn type_validator_checks_whole_value() {
+    let mut value = config();
+    update(&mut value, r#"{"limits":{"min":5}}"#).unwrap();
+
+    let err = value.validate().unwrap_err();
+    assert_eq!(err.path(), "limits");
+    assert_eq!(err.message(), "min 5 exceeds max 2");
+
+    update(&mut value, r#"{"limits":{"max":10}}"#).unwrap();
+    value.validate().unwrap();
+}
+
+#[test]
+fn validate_on_load_accepts_intermediate_layers() {
+    let mut value = Limits { min: 1, max: 2 };
+    update(&mut value, r#"{"min":5}"#).unwrap();
+    assert!(value.validate().is_err());
+    update(&mut value, r#"{"max":5}"#).unwrap();
+    value.validate().unwrap();
+}
+
+#[test]
+fn validate_on_update_fails_layer() {
+    #[derive(Debug, PartialEq, Deserialize, Figa)]
+    #[figa(validate = "checked_ordered", validate_on = update)]
+    struct Checked {
+        min: u32,
+        max: u32,
+    }
+
+    fn checked_ordered(value: &Checked) -> Result<(), &'static str> {
+        if value.min <= value.max {
+            Ok(())
+        } else {
+            Err("min exceeds max")
+        }
+    }
+
+    let mut value = Checked { min: 1, max: 2 };
+    update(&mut value, r#"{"max":3}"#).unwrap();
+    assert_eq!(value, Checked { min: 1, max: 3 });
+
+    let err = update(&mut value, r#"{"min":5}"#).unwrap_err();
+    assert!(err.to_string().contains("min exceeds max"), "{}", err);
+    update(&mut value, r#"{"min":5,"max":10}"#).unwrap();
+    assert_eq!(value, Checked { min: 5, max: 10 });
+}